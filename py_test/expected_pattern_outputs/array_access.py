@@ -1,4 +1,7 @@
-from rs_hexpyt.primitives import Dollar, Struct, BitField, IntStruct, u8, u16, u24, u32, u48, u64, u96, u128, s8, s16, s24, s32, s48, s64, s96, s128, Float, double, char, char16, Bool, Padding, Array, Enum, sizeof, addressof
+from rs_hexpyt.primitives import Dollar, Struct, BitField, IntStruct, u8, u16, u24, u32, u48, u64, u96, u128, s8, s16, s24, s32, s48, s64, s96, s128, Float, double, char, char16, Bool, Padding, Array, String, Enum, namespace, sizeof, addressof, hexpat_div, hexpat_wrap, placed
+
+# Every pattern placed outside of structs, in the order they were placed. Defining a name again doesn't remove the previous pattern
+placed_patterns: list[tuple[str, Struct]] = []
 
 # Template to read from a file. follow the instructions.
 # _dollar___offset has this name so it doesn't clash with others. Feel free to rename it.
@@ -11,6 +14,6 @@ else:
 _dollar___offset = Dollar(0x00, byts)
 # End of template
 
-a: Array[u8] = Array(u8, 5) @ Dollar(0x00, byts)
-b: u8
+a: Array[u8] = placed(placed_patterns, "a", Array(u8, 5) @ Dollar(0x00, byts))
+b: u8 = 0
 b = a[3]
@@ -1,4 +1,7 @@
-from rs_hexpyt.primitives import Dollar, Struct, BitField, IntStruct, u8, u16, u24, u32, u48, u64, u96, u128, s8, s16, s24, s32, s48, s64, s96, s128, Float, double, char, char16, Bool, Padding, Array, Enum, sizeof, addressof
+from rs_hexpyt.primitives import Dollar, Struct, BitField, IntStruct, u8, u16, u24, u32, u48, u64, u96, u128, s8, s16, s24, s32, s48, s64, s96, s128, Float, double, char, char16, Bool, Padding, Array, String, Enum, namespace, sizeof, addressof, hexpat_div, hexpat_wrap, placed
+
+# Every pattern placed outside of structs, in the order they were placed. Defining a name again doesn't remove the previous pattern
+placed_patterns: list[tuple[str, Struct]] = []
 
 # Template to read from a file. follow the instructions.
 # _dollar___offset has this name so it doesn't clash with others. Feel free to rename it.
@@ -10,7 +13,13 @@ else:
         byts = f.read()
 _dollar___offset = Dollar(0x00, byts)
 # End of template
-# This comment is here just so the test fails, attribute arguments are not forseen to be supported yet
+
+def name_of(value):
+    return "flags"
+
+def twice(value):
+    return value * 2
+
 class A(Struct):
     def __init__(self, name: str=""):
         """
@@ -27,7 +36,10 @@ class A(Struct):
         if isinstance(_dollar___offset, IntStruct):
             _dollar___offset = _dollar___offset.to_dollar()
         _dollar___offset_copy = _dollar___offset.copy()
-        self.b: u8 = u8('b') @ _dollar___offset
+        self.b: u8 = (u8() @ _dollar___offset).set_attributes({"color": "#000000"})
+        self.c: u8 = (u8() @ _dollar___offset).set_attributes({"format": name_of, "transform": twice})
 
         super().init_struct(_dollar___offset_copy, _dollar___offset.copy())
         return self
+
+a: A = placed(placed_patterns, "a", A() @ Dollar(0x00, byts))
@@ -1,4 +1,7 @@
-from rs_hexpyt.primitives import Dollar, Struct, BitField, IntStruct, u8, u16, u24, u32, u48, u64, u96, u128, s8, s16, s24, s32, s48, s64, s96, s128, Float, double, char, char16, Bool, Padding, Array, Enum, sizeof, addressof
+from rs_hexpyt.primitives import Dollar, Struct, BitField, IntStruct, u8, u16, u24, u32, u48, u64, u96, u128, s8, s16, s24, s32, s48, s64, s96, s128, Float, double, char, char16, Bool, Padding, Array, String, Enum, namespace, sizeof, addressof, hexpat_div, hexpat_wrap, placed
+
+# Every pattern placed outside of structs, in the order they were placed. Defining a name again doesn't remove the previous pattern
+placed_patterns: list[tuple[str, Struct]] = []
 
 # Template to read from a file. follow the instructions.
 # _dollar___offset has this name so it doesn't clash with others. Feel free to rename it.
@@ -11,36 +14,54 @@ else:
 _dollar___offset = Dollar(0x00, byts)
 # End of template
 
-a: u8 = u8() @ (1 + 1)
-a: u8 = u8() @ (1 - 1)
-a: u8 = u8() @ (1 * 1)
-a: u8 = u8() @ (1 / 1)
-a: u8 = u8() @ (1 == 1)
-a: u8 = u8() @ (1 != 1)
-a: u8 = u8() @ (1 % 1)
-a: u8 = u8() @ (1 << 1)
-a: u8 = u8() @ (1 >> 1)
-a: u8 = u8() @ (1 & 1)
-a: u8 = u8() @ (1 ^ 1)
-a: u8 = u8() @ (1 | 1)
-a: u8 = u8() @ (1 >= 1)
-a: u8 = u8() @ (1 <= 1)
-a: u8 = u8() @ (1 > 1)
-a: u8 = u8() @ (1 < 1)
-a: u8 = u8() @ (1 and 1)
-a: u8 = u8() @ (1 ^ 1)
-a: u8 = u8() @ (1 or 1)
-a: u8 = u8() @ 1
+a: u8 = hexpat_wrap(1 + 1, 8, False)
+a: u8 = hexpat_wrap(1 - 1, 8, False)
+a: u8 = hexpat_wrap(1 * 1, 8, False)
+a: u8 = hexpat_wrap(1 // 1, 8, False)
+a: u8 = hexpat_wrap(1 == 1, 8, False)
+a: u8 = hexpat_wrap(1 != 1, 8, False)
+a: u8 = hexpat_wrap(1 % 1, 8, False)
+a: u8 = hexpat_wrap(1 << 1, 8, False)
+a: u8 = hexpat_wrap(1 >> 1, 8, False)
+a: u8 = hexpat_wrap(1 & 1, 8, False)
+a: u8 = hexpat_wrap(1 ^ 1, 8, False)
+a: u8 = hexpat_wrap(1 | 1, 8, False)
+a: u8 = hexpat_wrap(1 >= 1, 8, False)
+a: u8 = hexpat_wrap(1 <= 1, 8, False)
+a: u8 = hexpat_wrap(1 > 1, 8, False)
+a: u8 = hexpat_wrap(1 < 1, 8, False)
+a: u8 = hexpat_wrap(1 and 1, 8, False)
+a: u8 = hexpat_wrap(bool(1) != bool(1), 8, False)
+a: u8 = hexpat_wrap(1 or 1, 8, False)
+a: u8 = 1
 
 def fun():
-    a: u8
-    a += 1
-    a -= 1
-    a *= 1
-    a /= 1
-    a %= 1
-    a >>= 1
-    a <<= 1
-    a |= 1
-    a &= 1
-    a ^= 1
+    a: u8 = 0
+    a = hexpat_wrap(a + 1, 8, False)
+    a = hexpat_wrap(a - 1, 8, False)
+    a = hexpat_wrap(a * 1, 8, False)
+    a = hexpat_wrap(a // 1, 8, False)
+    a = hexpat_wrap(a % 1, 8, False)
+    a = hexpat_wrap(a >> 1, 8, False)
+    a = hexpat_wrap(a << 1, 8, False)
+    a = hexpat_wrap(a | 1, 8, False)
+    a = hexpat_wrap(a & 1, 8, False)
+    a = hexpat_wrap(a ^ 1, 8, False)
+
+b: u8 = hexpat_wrap((1 + 2) * 3, 8, False)
+b: u8 = hexpat_wrap(1 - (2 - 3), 8, False)
+b: u8 = hexpat_wrap(1 - 2 - 3, 8, False)
+b: u8 = hexpat_wrap(1 & 2 == 0, 8, False)
+b: u8 = hexpat_wrap(1 & (2 == 0), 8, False)
+b: u8 = hexpat_wrap((1 < 2) == (2 < 3), 8, False)
+b: u8 = hexpat_wrap(1 << 2 + 3, 8, False)
+b: u8 = hexpat_wrap((1 or 0) and 1, 8, False)
+
+def checksum():
+    total: s8 = 127
+    total = hexpat_wrap(total + 1, 8, True)
+    total = hexpat_wrap(total * (2 + 1), 8, True)
+    digest: u16 = 0
+    digest = hexpat_wrap(digest << 15 + 1, 16, False)
+    letter: char = "a"
+    letter = chr(hexpat_wrap(ord(letter) + 1, 8, False))
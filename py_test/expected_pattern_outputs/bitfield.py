@@ -1,4 +1,7 @@
-from rs_hexpyt.primitives import Dollar, Struct, BitField, IntStruct, u8, u16, u24, u32, u48, u64, u96, u128, s8, s16, s24, s32, s48, s64, s96, s128, Float, double, char, char16, Bool, Padding, Array, Enum, sizeof, addressof
+from rs_hexpyt.primitives import Dollar, Struct, BitField, IntStruct, u8, u16, u24, u32, u48, u64, u96, u128, s8, s16, s24, s32, s48, s64, s96, s128, Float, double, char, char16, Bool, Padding, Array, String, Enum, namespace, sizeof, addressof, hexpat_div, hexpat_wrap, placed
+
+# Every pattern placed outside of structs, in the order they were placed. Defining a name again doesn't remove the previous pattern
+placed_patterns: list[tuple[str, Struct]] = []
 
 # Template to read from a file. follow the instructions.
 # _dollar___offset has this name so it doesn't clash with others. Feel free to rename it.
@@ -10,3 +13,105 @@ else:
         byts = f.read()
 _dollar___offset = Dollar(0x00, byts)
 # End of template
+
+class A(BitField):
+    def __init__(self, name: str=""):
+        """
+        bitfield
+
+        Args:
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+
+        super().__init__(name)
+
+    def __matmul__(self, _dollar___offset):
+        if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):
+            raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {type(_dollar___offset)} was used instead')
+        if isinstance(_dollar___offset, IntStruct):
+            _dollar___offset = _dollar___offset.to_dollar()
+        _dollar___offset_copy = _dollar___offset.copy()
+        self.a = self.read_bits(_dollar___offset, 5)
+        self.b = self.read_bits(_dollar___offset, 3)
+        self.c = self.read_bits(_dollar___offset, 4)
+        super().init_bitfield(_dollar___offset_copy, _dollar___offset)
+        return self
+
+class Flags(BitField):
+    def __init__(self, name: str=""):
+        """
+        bitfield
+
+        Args:
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+
+        super().__init__(name)
+
+    def __matmul__(self, _dollar___offset):
+        if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):
+            raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {type(_dollar___offset)} was used instead')
+        if isinstance(_dollar___offset, IntStruct):
+            _dollar___offset = _dollar___offset.to_dollar()
+        _dollar___offset_copy = _dollar___offset.copy()
+        self.a = self.read_bits(_dollar___offset, 4)
+        self.read_bits(_dollar___offset, 2)
+        self.b = self.read_bits(_dollar___offset, 4, signed=True)
+        self.c = self.read_bits(_dollar___offset, 1, Bool)
+        if self.a == 1:
+            self.d = self.read_bits(_dollar___offset, self.a + 1)
+        super().init_bitfield(_dollar___offset_copy, _dollar___offset)
+        return self
+
+flags: Flags = placed(placed_patterns, "flags", Flags() @ Dollar(0x00, byts))
+
+class Header(BitField):
+    _bit_field___msb_first = True
+    _bit_field___size = 16
+
+    def __init__(self, name: str=""):
+        """
+        bitfield
+
+        Args:
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+
+        super().__init__(name)
+
+    def __matmul__(self, _dollar___offset):
+        if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):
+            raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {type(_dollar___offset)} was used instead')
+        if isinstance(_dollar___offset, IntStruct):
+            _dollar___offset = _dollar___offset.to_dollar()
+        _dollar___offset_copy = _dollar___offset.copy()
+        self.version = self.read_bits(_dollar___offset, 4)
+        self.kind = self.read_bits(_dollar___offset, 12)
+        super().init_bitfield(_dollar___offset_copy, _dollar___offset)
+        return self
+
+header: Header = placed(placed_patterns, "header", Header().set_big_endian() @ Dollar(0x02, byts))
+
+class Pair(Struct):
+    def __init__(self, name: str=""):
+        """
+        struct
+
+        Args
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+        super().__init__(name)
+
+    def __matmul__(self, _dollar___offset):
+        if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):
+            raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {{type(_dollar___offset)}} was used instead')
+        if isinstance(_dollar___offset, IntStruct):
+            _dollar___offset = _dollar___offset.to_dollar()
+        _dollar___offset_copy = _dollar___offset.copy()
+        self.first: Flags = Flags().set_parent(self) @ _dollar___offset
+        self.second: Flags = Flags().set_parent(self) @ _dollar___offset
+
+        super().init_struct(_dollar___offset_copy, _dollar___offset.copy())
+        return self
+
+pair: Pair = placed(placed_patterns, "pair", Pair() @ Dollar(0x04, byts))
@@ -1,4 +1,7 @@
-from rs_hexpyt.primitives import Dollar, Struct, BitField, IntStruct, u8, u16, u24, u32, u48, u64, u96, u128, s8, s16, s24, s32, s48, s64, s96, s128, Float, double, char, char16, Bool, Padding, Array, Enum, sizeof, addressof
+from rs_hexpyt.primitives import Dollar, Struct, BitField, IntStruct, u8, u16, u24, u32, u48, u64, u96, u128, s8, s16, s24, s32, s48, s64, s96, s128, Float, double, char, char16, Bool, Padding, Array, String, Enum, namespace, sizeof, addressof, hexpat_div, hexpat_wrap, placed
+
+# Every pattern placed outside of structs, in the order they were placed. Defining a name again doesn't remove the previous pattern
+placed_patterns: list[tuple[str, Struct]] = []
 
 # Template to read from a file. follow the instructions.
 # _dollar___offset has this name so it doesn't clash with others. Feel free to rename it.
@@ -10,3 +13,62 @@ else:
         byts = f.read()
 _dollar___offset = Dollar(0x00, byts)
 # End of template
+
+def myfunc():
+    i: u8 = 0
+    while i < 10:
+        break
+        i = hexpat_wrap(i + 1, 8, False)
+
+class A(Struct):
+    def __init__(self, name: str=""):
+        """
+        struct
+
+        Args
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+        super().__init__(name)
+
+    def __matmul__(self, _dollar___offset):
+        if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):
+            raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {{type(_dollar___offset)}} was used instead')
+        if isinstance(_dollar___offset, IntStruct):
+            _dollar___offset = _dollar___offset.to_dollar()
+        _dollar___offset_copy = _dollar___offset.copy()
+        self.a: u8 = u8() @ _dollar___offset
+        if self.a == 0:
+            self.break_()
+            super().init_struct(_dollar___offset_copy, _dollar___offset.copy())
+            return self
+
+        super().init_struct(_dollar___offset_copy, _dollar___offset.copy())
+        return self
+
+def stop():
+    raise NotImplementedError("break is only allowed inside of loops and structs")
+
+class Stopped(Struct):
+    def __init__(self, name: str=""):
+        """
+        struct
+
+        Args
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+        super().__init__(name)
+
+    def __matmul__(self, _dollar___offset):
+        if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):
+            raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {{type(_dollar___offset)}} was used instead')
+        if isinstance(_dollar___offset, IntStruct):
+            _dollar___offset = _dollar___offset.to_dollar()
+        _dollar___offset_copy = _dollar___offset.copy()
+        self.break_()
+        super().init_struct(_dollar___offset_copy, _dollar___offset.copy())
+        return self
+
+        super().init_struct(_dollar___offset_copy, _dollar___offset.copy())
+        return self
+
+raise NotImplementedError("break is only allowed inside of loops and structs")
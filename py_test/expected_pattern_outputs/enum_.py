@@ -1,4 +1,7 @@
-from rs_hexpyt.primitives import Dollar, Struct, BitField, IntStruct, u8, u16, u24, u32, u48, u64, u96, u128, s8, s16, s24, s32, s48, s64, s96, s128, Float, double, char, char16, Bool, Padding, Array, Enum, sizeof, addressof
+from rs_hexpyt.primitives import Dollar, Struct, BitField, IntStruct, u8, u16, u24, u32, u48, u64, u96, u128, s8, s16, s24, s32, s48, s64, s96, s128, Float, double, char, char16, Bool, Padding, Array, String, Enum, namespace, sizeof, addressof, hexpat_div, hexpat_wrap, placed
+
+# Every pattern placed outside of structs, in the order they were placed. Defining a name again doesn't remove the previous pattern
+placed_patterns: list[tuple[str, Struct]] = []
 
 # Template to read from a file. follow the instructions.
 # _dollar___offset has this name so it doesn't clash with others. Feel free to rename it.
@@ -10,3 +13,62 @@ else:
         byts = f.read()
 _dollar___offset = Dollar(0x00, byts)
 # End of template
+
+class Kind(Enum):
+    Unknown = 0
+    Image = 5
+    Sound = Image + 1
+    Reserved = 10
+    Other = 20 + 1
+    _enum__dict___ = {Unknown: "Unknown", Image: "Image", Sound: "Sound", Reserved: "Reserved", Other: "Other"}
+    _enum__ranges___ = [(10, 20, "Reserved")]
+
+    def __init__(self, value=None, name: str=""):
+        """
+        enum
+
+        Args:
+            value (optional): Defaults to None.
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+        super().__init__(u8, value, name)
+
+kind: Kind = placed(placed_patterns, "kind", Kind() @ Dollar(0x00, byts))
+is_image: Bool = kind == Kind.Image
+
+class Permissions(Enum):
+    Read = 1
+    Write = 2
+    Execute = 4
+    _enum__dict___ = {Read: "Read", Write: "Write", Execute: "Execute"}
+    _enum__flags___ = True
+
+    def __init__(self, value=None, name: str=""):
+        """
+        enum
+
+        Args:
+            value (optional): Defaults to None.
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+        super().__init__(u8, value, name)
+
+class Reserved(Enum):
+    _A_ = 0
+    mro = _A_ + 1
+    h__B__ = mro + 1
+    _enum__dict___ = {_A_: "_A_", mro: "mro", h__B__: "__B__"}
+    _enum__python__names___ = {"__B__": "h__B__"}
+    _enum__flags___ = True
+
+    def __init__(self, value=None, name: str=""):
+        """
+        enum
+
+        Args:
+            value (optional): Defaults to None.
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+        super().__init__(u8, value, name)
+
+reserved: Reserved = placed(placed_patterns, "reserved", Reserved() @ Dollar(0x01, byts))
@@ -0,0 +1,42 @@
+from rs_hexpyt.primitives import Dollar, Struct, BitField, IntStruct, u8, u16, u24, u32, u48, u64, u96, u128, s8, s16, s24, s32, s48, s64, s96, s128, Float, double, char, char16, Bool, Padding, Array, String, Enum, namespace, sizeof, addressof, hexpat_div, hexpat_wrap, placed
+
+# Every pattern placed outside of structs, in the order they were placed. Defining a name again doesn't remove the previous pattern
+placed_patterns: list[tuple[str, Struct]] = []
+
+# Template to read from a file. follow the instructions.
+# _dollar___offset1 has this name so it doesn't clash with others. Feel free to rename it.
+if True: # Change this from "if True" to "if False", then put the file path below.
+    byts1 = b''
+else:
+    file_path = "" # Put the file path here and change the above "if True" to "if False".
+    with open(file_path, "rb") as f:
+        byts1 = f.read()
+_dollar___offset1 = Dollar(0x00, byts1)
+# End of template
+
+byts: u8 = placed(placed_patterns, "byts", u8() @ Dollar(0x00, byts1))
+_dollar___offset: u8 = placed(placed_patterns, "_dollar___offset", u8() @ Dollar(0x01, byts1))
+
+class Entry(Struct):
+    def __init__(self, name: str=""):
+        """
+        struct
+
+        Args
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+        super().__init__(name)
+
+    def __matmul__(self, _dollar___offset1):
+        if not (isinstance(_dollar___offset1, Dollar) or isinstance(_dollar___offset1, IntStruct)):
+            raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {{type(_dollar___offset1)}} was used instead')
+        if isinstance(_dollar___offset1, IntStruct):
+            _dollar___offset1 = _dollar___offset1.to_dollar()
+        _dollar___offset_copy1 = _dollar___offset1.copy()
+        self._dollar___offset_copy: u8 = u8() @ _dollar___offset1
+        self.data: Array[u8] = Array(u8, self._dollar___offset_copy) @ _dollar___offset1
+
+        super().init_struct(_dollar___offset_copy1, _dollar___offset1.copy())
+        return self
+
+entry: Entry = placed(placed_patterns, "entry", Entry() @ Dollar(0x02, byts1))
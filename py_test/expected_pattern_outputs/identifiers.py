@@ -0,0 +1,85 @@
+from rs_hexpyt.primitives import Dollar, Struct, BitField, IntStruct, u8, u16, u24, u32, u48, u64, u96, u128, s8, s16, s24, s32, s48, s64, s96, s128, Float, double, char, char16, Bool, Padding, Array, String, Enum, namespace, sizeof, addressof, hexpat_div, hexpat_wrap, placed
+
+# Every pattern placed outside of structs, in the order they were placed. Defining a name again doesn't remove the previous pattern
+placed_patterns: list[tuple[str, Struct]] = []
+
+# Template to read from a file. follow the instructions.
+# _dollar___offset has this name so it doesn't clash with others. Feel free to rename it.
+if True: # Change this from "if True" to "if False", then put the file path below.
+    byts = b''
+else:
+    file_path = "" # Put the file path here and change the above "if True" to "if False".
+    with open(file_path, "rb") as f:
+        byts = f.read()
+_dollar___offset = Dollar(0x00, byts)
+# End of template
+
+class Kind(Enum):
+    None_ = 0
+    class_ = 3
+    value_ = class_ + 1
+    _enum__dict____ = value_ + 1
+    _enum__dict___ = {None_: "None", class_: "class", value_: "value", _enum__dict____: "_enum__dict___"}
+    _enum__python__names___ = {"None": "None_", "class": "class_", "value": "value_", "_enum__dict___": "_enum__dict____"}
+
+    def __init__(self, value=None, name: str=""):
+        """
+        enum
+
+        Args:
+            value (optional): Defaults to None.
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+        super().__init__(u8, value, name)
+
+class Header(Struct):
+    def __init__(self, name: str=""):
+        """
+        struct
+
+        Args
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+        super().__init__(name)
+
+    def __matmul__(self, _dollar___offset):
+        if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):
+            raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {{type(_dollar___offset)}} was used instead')
+        if isinstance(_dollar___offset, IntStruct):
+            _dollar___offset = _dollar___offset.to_dollar()
+        _dollar___offset_copy = _dollar___offset.copy()
+        self.from_: u8 = u8().set_name("from") @ _dollar___offset
+        self.size_1: u8 = u8().set_name("size") @ _dollar___offset
+        self.size_: u8 = u8() @ _dollar___offset
+        self.type_: Kind = Kind().set_parent(self).set_name("type") @ _dollar___offset
+        self.data: Array[u8] = Array(u8, self.size_1) @ _dollar___offset
+
+        super().init_struct(_dollar___offset_copy, _dollar___offset.copy())
+        return self
+
+class Flags(BitField):
+    def __init__(self, name: str=""):
+        """
+        bitfield
+
+        Args:
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+
+        super().__init__(name)
+
+    def __matmul__(self, _dollar___offset):
+        if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):
+            raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {type(_dollar___offset)} was used instead')
+        if isinstance(_dollar___offset, IntStruct):
+            _dollar___offset = _dollar___offset.to_dollar()
+        _dollar___offset_copy = _dollar___offset.copy()
+        self.lambda_ = self.read_bits(_dollar___offset, 4).set_name("lambda")
+        self.len_ = self.read_bits(_dollar___offset, 4).set_name("len")
+        self._bit_field___size_ = self.read_bits(_dollar___offset, 8).set_name("_bit_field___size")
+        super().init_bitfield(_dollar___offset_copy, _dollar___offset)
+        return self
+
+self_: Header = placed(placed_patterns, "self", Header().set_name("self") @ Dollar(0x00, byts))
+flags: Flags = placed(placed_patterns, "flags", Flags() @ Dollar(0x04, byts))
+is_none: Bool = self_.type_ == Kind.None_
@@ -0,0 +1,222 @@
+from rs_hexpyt.primitives import Dollar, Struct, BitField, IntStruct, u8, u16, u24, u32, u48, u64, u96, u128, s8, s16, s24, s32, s48, s64, s96, s128, Float, double, char, char16, Bool, Padding, Array, String, Enum, namespace, sizeof, addressof, hexpat_div, hexpat_wrap, placed
+
+# Every pattern placed outside of structs, in the order they were placed. Defining a name again doesn't remove the previous pattern
+placed_patterns: list[tuple[str, Struct]] = []
+
+# Template to read from a file. follow the instructions.
+# _dollar___offset has this name so it doesn't clash with others. Feel free to rename it.
+if True: # Change this from "if True" to "if False", then put the file path below.
+    byts = b''
+else:
+    file_path = "" # Put the file path here and change the above "if True" to "if False".
+    with open(file_path, "rb") as f:
+        byts = f.read()
+_dollar___offset = Dollar(0x00, byts)
+# End of template
+
+class Base(Struct):
+    def __init__(self, name: str=""):
+        """
+        struct
+
+        Args
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+        super().__init__(name)
+
+    def __matmul__(self, _dollar___offset):
+        if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):
+            raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {{type(_dollar___offset)}} was used instead')
+        if isinstance(_dollar___offset, IntStruct):
+            _dollar___offset = _dollar___offset.to_dollar()
+        _dollar___offset_copy = _dollar___offset.copy()
+        self.count: u8 = u8() @ _dollar___offset
+
+        super().init_struct(_dollar___offset_copy, _dollar___offset.copy())
+        return self
+
+class Derived(Base):
+    def __init__(self, name: str=""):
+        """
+        struct
+
+        Args
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+        super().__init__(name)
+
+    def __matmul__(self, _dollar___offset):
+        if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):
+            raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {{type(_dollar___offset)}} was used instead')
+        if isinstance(_dollar___offset, IntStruct):
+            _dollar___offset = _dollar___offset.to_dollar()
+        _dollar___offset_copy = _dollar___offset.copy()
+        Base.__matmul__(self, _dollar___offset)
+        self.data: Array[u8] = Array(u8, self.count) @ _dollar___offset
+
+        super().init_struct(_dollar___offset_copy, _dollar___offset.copy())
+        return self
+
+derived: Derived = placed(placed_patterns, "derived", Derived() @ Dollar(0x00, byts))
+
+class ns(namespace(locals(), "ns")):
+    class Header(Struct):
+        def __init__(self, name: str=""):
+            """
+            struct
+
+            Args
+                name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+            """
+            super().__init__(name)
+
+        def __matmul__(self, _dollar___offset):
+            if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):
+                raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {{type(_dollar___offset)}} was used instead')
+            if isinstance(_dollar___offset, IntStruct):
+                _dollar___offset = _dollar___offset.to_dollar()
+            _dollar___offset_copy = _dollar___offset.copy()
+            self.kind: u8 = u8() @ _dollar___offset
+
+            super().init_struct(_dollar___offset_copy, _dollar___offset.copy())
+            return self
+
+    class Tagged(Header):
+        def __init__(self, name: str=""):
+            """
+            struct
+
+            Args
+                name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+            """
+            super().__init__(name)
+
+        def __matmul__(self, _dollar___offset):
+            if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):
+                raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {{type(_dollar___offset)}} was used instead')
+            if isinstance(_dollar___offset, IntStruct):
+                _dollar___offset = _dollar___offset.to_dollar()
+            _dollar___offset_copy = _dollar___offset.copy()
+            ns.Header.__matmul__(self, _dollar___offset)
+            self.tag: u8 = u8() @ _dollar___offset
+
+            super().init_struct(_dollar___offset_copy, _dollar___offset.copy())
+            return self
+
+class Message(ns.Tagged):
+    def __init__(self, name: str=""):
+        """
+        struct
+
+        Args
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+        super().__init__(name)
+
+    def __matmul__(self, _dollar___offset):
+        if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):
+            raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {{type(_dollar___offset)}} was used instead')
+        if isinstance(_dollar___offset, IntStruct):
+            _dollar___offset = _dollar___offset.to_dollar()
+        _dollar___offset_copy = _dollar___offset.copy()
+        ns.Tagged.__matmul__(self, _dollar___offset)
+        self.body: Array[u8] = Array(u8, self.kind) @ _dollar___offset
+
+        super().init_struct(_dollar___offset_copy, _dollar___offset.copy())
+        return self
+
+message: Message = placed(placed_patterns, "message", Message() @ Dollar(0x02, byts))
+Alias = Base
+
+class ViaAlias(Alias):
+    def __init__(self, name: str=""):
+        """
+        struct
+
+        Args
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+        super().__init__(name)
+
+    def __matmul__(self, _dollar___offset):
+        if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):
+            raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {{type(_dollar___offset)}} was used instead')
+        if isinstance(_dollar___offset, IntStruct):
+            _dollar___offset = _dollar___offset.to_dollar()
+        _dollar___offset_copy = _dollar___offset.copy()
+        Alias.__matmul__(self, _dollar___offset)
+        self.extra: Array[u8] = Array(u8, self.count) @ _dollar___offset
+
+        super().init_struct(_dollar___offset_copy, _dollar___offset.copy())
+        return self
+
+via_alias: ViaAlias = placed(placed_patterns, "via_alias", ViaAlias() @ Dollar(0x05, byts))
+
+class Choice(Struct):
+    def __init__(self, name: str=""):
+        """
+        union
+
+        Args
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+        super().__init__(name)
+
+    def __matmul__(self, _dollar___offset):
+        if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):
+            raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {{type(_dollar___offset)}} was used instead')
+        if isinstance(_dollar___offset, IntStruct):
+            _dollar___offset = _dollar___offset.to_dollar()
+        _dollar___offset_copy = _dollar___offset.copy()
+        self.small: u8 = u8() @ _dollar___offset_copy.copy()
+        self.large: u16 = u16() @ _dollar___offset_copy.copy()
+
+        super().init_union(_dollar___offset_copy)
+        _dollar___offset += self.size()
+        return self
+
+class Chosen(Choice):
+    def __init__(self, name: str=""):
+        """
+        struct
+
+        Args
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+        super().__init__(name)
+
+    def __matmul__(self, _dollar___offset):
+        if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):
+            raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {{type(_dollar___offset)}} was used instead')
+        if isinstance(_dollar___offset, IntStruct):
+            _dollar___offset = _dollar___offset.to_dollar()
+        _dollar___offset_copy = _dollar___offset.copy()
+        Choice.__matmul__(self, _dollar___offset)
+        self.after: Array[u8] = Array(u8, self.small) @ _dollar___offset
+
+        super().init_struct(_dollar___offset_copy, _dollar___offset.copy())
+        return self
+
+chosen: Chosen = placed(placed_patterns, "chosen", Chosen() @ Dollar(0x08, byts))
+
+class Orphan(Struct):
+    def __init__(self, name: str=""):
+        """
+        struct
+
+        Args
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+        super().__init__(name)
+
+    def __matmul__(self, _dollar___offset):
+        if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):
+            raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {{type(_dollar___offset)}} was used instead')
+        if isinstance(_dollar___offset, IntStruct):
+            _dollar___offset = _dollar___offset.to_dollar()
+        _dollar___offset_copy = _dollar___offset.copy()
+        raise NotImplementedError("Missing isn't a struct or union")
+        self.x: u8 = u8() @ _dollar___offset
+
+        super().init_struct(_dollar___offset_copy, _dollar___offset.copy())
+        return self
@@ -1,4 +1,7 @@
-from rs_hexpyt.primitives import Dollar, Struct, BitField, IntStruct, u8, u16, u24, u32, u48, u64, u96, u128, s8, s16, s24, s32, s48, s64, s96, s128, Float, double, char, char16, Bool, Padding, Array, Enum, sizeof, addressof
+from rs_hexpyt.primitives import Dollar, Struct, BitField, IntStruct, u8, u16, u24, u32, u48, u64, u96, u128, s8, s16, s24, s32, s48, s64, s96, s128, Float, double, char, char16, Bool, Padding, Array, String, Enum, namespace, sizeof, addressof, hexpat_div, hexpat_wrap, placed
+
+# Every pattern placed outside of structs, in the order they were placed. Defining a name again doesn't remove the previous pattern
+placed_patterns: list[tuple[str, Struct]] = []
 
 # Template to read from a file. follow the instructions.
 # _dollar___offset has this name so it doesn't clash with others. Feel free to rename it.
@@ -10,3 +13,76 @@ else:
         byts = f.read()
 _dollar___offset = Dollar(0x00, byts)
 # End of template
+
+class my(namespace(locals(), "my")):
+    class inner(namespace(locals(), "inner")):
+        class Flag(Struct):
+            def __init__(self, name: str=""):
+                """
+                struct
+
+                Args
+                    name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+                """
+                super().__init__(name)
+
+            def __matmul__(self, _dollar___offset):
+                if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):
+                    raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {{type(_dollar___offset)}} was used instead')
+                if isinstance(_dollar___offset, IntStruct):
+                    _dollar___offset = _dollar___offset.to_dollar()
+                _dollar___offset_copy = _dollar___offset.copy()
+                self.bits: u8 = u8() @ _dollar___offset
+
+                super().init_struct(_dollar___offset_copy, _dollar___offset.copy())
+                return self
+
+    class Version(Struct):
+        def __init__(self, name: str=""):
+            """
+            struct
+
+            Args
+                name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+            """
+            super().__init__(name)
+
+        def __matmul__(self, _dollar___offset):
+            if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):
+                raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {{type(_dollar___offset)}} was used instead')
+            if isinstance(_dollar___offset, IntStruct):
+                _dollar___offset = _dollar___offset.to_dollar()
+            _dollar___offset_copy = _dollar___offset.copy()
+            self.major: u8 = u8() @ _dollar___offset
+
+            super().init_struct(_dollar___offset_copy, _dollar___offset.copy())
+            return self
+
+    class Header(Struct):
+        def __init__(self, name: str=""):
+            """
+            struct
+
+            Args
+                name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+            """
+            super().__init__(name)
+
+        def __matmul__(self, _dollar___offset):
+            if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):
+                raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {{type(_dollar___offset)}} was used instead')
+            if isinstance(_dollar___offset, IntStruct):
+                _dollar___offset = _dollar___offset.to_dollar()
+            _dollar___offset_copy = _dollar___offset.copy()
+            self.version: my.Version = my.Version().set_parent(self) @ _dollar___offset
+            self.flag: my.inner.Flag = my.inner.Flag().set_parent(self) @ _dollar___offset
+
+            super().init_struct(_dollar___offset_copy, _dollar___offset.copy())
+            return self
+
+class a(namespace(locals(), "a")):
+    class b(namespace(locals(), "b")):
+        pass
+
+h: my.Header = placed(placed_patterns, "h", my.Header() @ Dollar(0x00, byts))
+version: my.Version = h.version
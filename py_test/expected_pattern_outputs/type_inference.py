@@ -0,0 +1,30 @@
+from rs_hexpyt.primitives import Dollar, Struct, BitField, IntStruct, u8, u16, u24, u32, u48, u64, u96, u128, s8, s16, s24, s32, s48, s64, s96, s128, Float, double, char, char16, Bool, Padding, Array, String, Enum, namespace, sizeof, addressof, hexpat_div, hexpat_wrap, placed
+
+# Every pattern placed outside of structs, in the order they were placed. Defining a name again doesn't remove the previous pattern
+placed_patterns: list[tuple[str, Struct]] = []
+
+# Template to read from a file. follow the instructions.
+# _dollar___offset has this name so it doesn't clash with others. Feel free to rename it.
+if True: # Change this from "if True" to "if False", then put the file path below.
+    byts = b''
+else:
+    file_path = "" # Put the file path here and change the above "if True" to "if False".
+    with open(file_path, "rb") as f:
+        byts = f.read()
+_dollar___offset = Dollar(0x00, byts)
+# End of template
+
+def average():
+    count: u128 = 4
+    total: u128 = hexpat_wrap(count * 3, 128, False)
+    a: u8 = 7
+    b: s8 = -7
+    c: double = 7.0
+    d: double = c / 2
+    e: u8 = hexpat_wrap(a // 2, 8, False)
+    f: s8 = hexpat_wrap(hexpat_div(b, 2), 8, True)
+    a = hexpat_wrap(a // 2, 8, False)
+    b = hexpat_wrap(hexpat_div(b, 2), 8, True)
+    c /= 2
+    g: double = 1e10 / 3
+    h: u8 = "text"
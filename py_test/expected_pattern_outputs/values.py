@@ -1,4 +1,7 @@
-from rs_hexpyt.primitives import Dollar, Struct, BitField, IntStruct, u8, u16, u24, u32, u48, u64, u96, u128, s8, s16, s24, s32, s48, s64, s96, s128, Float, double, char, char16, Bool, Padding, Array, Enum, sizeof, addressof
+from rs_hexpyt.primitives import Dollar, Struct, BitField, IntStruct, u8, u16, u24, u32, u48, u64, u96, u128, s8, s16, s24, s32, s48, s64, s96, s128, Float, double, char, char16, Bool, Padding, Array, String, Enum, namespace, sizeof, addressof, hexpat_div, hexpat_wrap, placed
+
+# Every pattern placed outside of structs, in the order they were placed. Defining a name again doesn't remove the previous pattern
+placed_patterns: list[tuple[str, Struct]] = []
 
 # Template to read from a file. follow the instructions.
 # _dollar___offset has this name so it doesn't clash with others. Feel free to rename it.
@@ -10,3 +13,18 @@ else:
         byts = f.read()
 _dollar___offset = Dollar(0x00, byts)
 # End of template
+
+quoted: str = "say \"hi\"\\n"
+accented: str = "caf\xc3\xa9"
+written: str = "caf\xc3\xa9"
+quote: char = "'"
+backslash: char = "\\"
+high: char = "\xff"
+yes: Bool = True
+max_: u64 = 0xFFFFFFFFFFFFFFFF
+magic: u128 = 0x0123456789ABCDEF0123456789ABCDEF
+mask: u8 = 0b1010
+octal: u8 = 0o17
+decimal: u32 = 18446744073709551615
+half: double = 0.5
+big: Float = 1e10
@@ -0,0 +1,38 @@
+from rs_hexpyt.primitives import Dollar, Struct, BitField, IntStruct, u8, u16, u24, u32, u48, u64, u96, u128, s8, s16, s24, s32, s48, s64, s96, s128, Float, double, char, char16, Bool, Padding, Array, String, Enum, namespace, sizeof, addressof, hexpat_div, hexpat_wrap, placed
+
+# Every pattern placed outside of structs, in the order they were placed. Defining a name again doesn't remove the previous pattern
+placed_patterns: list[tuple[str, Struct]] = []
+
+# Template to read from a file. follow the instructions.
+# _dollar___offset has this name so it doesn't clash with others. Feel free to rename it.
+if True: # Change this from "if True" to "if False", then put the file path below.
+    byts = b''
+else:
+    file_path = "" # Put the file path here and change the above "if True" to "if False".
+    with open(file_path, "rb") as f:
+        byts = f.read()
+_dollar___offset = Dollar(0x00, byts)
+# End of template
+
+def difference():
+    b: u8 = hexpat_wrap(1 - 2 - 3, 8, False)
+    return b
+
+def equality():
+    x: u8 = hexpat_wrap(1 == 1, 8, False)
+    return x
+
+def overflow():
+    total: s8 = 127
+    total = hexpat_wrap(total + 1, 8, True)
+    return total
+
+def assigned():
+    small: u8 = 0
+    small = hexpat_wrap(300, 8, False)
+    return small
+
+def next_letter():
+    letter: char = "a"
+    letter = chr(hexpat_wrap(ord(letter) + 1, 8, False))
+    return letter
@@ -0,0 +1,68 @@
+from rs_hexpyt import translate_file
+
+import ast
+import os
+
+# Every pattern in hostile_inputs tries to make the translation run `pwned`.
+# A pattern passes if it's refused with a ValueError, or if `pwned` only appears inside of string literals of the translation
+# and the translation doesn't call anything that runs code from a string
+
+if os.path.exists("hostile_inputs"):
+    in_dir = "hostile_inputs"
+    out_dir = "hostile_outputs"
+else:
+    in_dir = "py_test/hostile_inputs"
+    out_dir = "py_test/hostile_outputs"
+
+if not os.path.exists(out_dir):
+    os.mkdir(out_dir)
+
+red = "\x1B[31m"
+green = "\x1B[32m"
+clear = "\x1B[0m"
+
+# Calls that run code or commands from strings. Translations never make them, so any of them was injected
+DANGEROUS_CALLS = {"__import__", "eval", "exec", "compile", "getattr", "system", "popen"}
+
+def calls(tree: ast.AST):
+    for node in ast.walk(tree):
+        if isinstance(node, ast.Call):
+            if isinstance(node.func, ast.Name):
+                yield node.func.id
+            elif isinstance(node.func, ast.Attribute):
+                yield node.func.attr
+
+def names(tree: ast.AST):
+    for node in ast.walk(tree):
+        if isinstance(node, ast.Name):
+            yield node.id
+        elif isinstance(node, ast.Attribute):
+            yield node.attr
+        elif isinstance(node, (ast.FunctionDef, ast.ClassDef)):
+            yield node.name
+
+failed = False
+for pattern_input in sorted(os.listdir(in_dir)):
+    pattern_output = f"{out_dir}/{pattern_input.split('.')[0]}.py"
+    try:
+        translate_file(f"{in_dir}/{pattern_input}", pattern_output)
+    # Refused patterns raise ValueError. Anything else, like a panic of the translator, is a failure
+    except ValueError as error:
+        print(f"test {pattern_input} {green}passed{clear} (refused: {error})")
+        continue
+    except BaseException as error:
+        failed = True
+        print(f"test {pattern_input} {red}failed{clear} ({type(error).__name__}: {error})")
+        continue
+
+    with open(pattern_output, "r") as created_file:
+        tree = ast.parse(created_file.read())
+
+    if any("pwned" in name for name in names(tree)) or any(call in DANGEROUS_CALLS for call in calls(tree)):
+        failed = True
+        print(f"test {pattern_input} {red}failed{clear}")
+    else:
+        print(f"test {pattern_input} {green}passed{clear}")
+
+if failed:
+    exit(1)
@@ -0,0 +1,54 @@
+from rs_hexpyt import translate_file
+
+import os
+import runpy
+
+# Runs functions of translated patterns and checks what they return, for what matching the expected text can't show
+
+if os.path.exists("pattern_inputs"):
+    in_dir = "pattern_inputs"
+    out_dir = "runtime_outputs"
+else:
+    in_dir = "py_test/pattern_inputs"
+    out_dir = "py_test/runtime_outputs"
+
+if not os.path.exists(out_dir):
+    os.mkdir(out_dir)
+
+red = "\x1B[31m"
+green = "\x1B[32m"
+clear = "\x1B[0m"
+
+# pattern: {function: what it returns}
+RETURNS = {
+    "wrapping.hexpat": {
+        "difference": 252,
+        "equality": 1,
+        "overflow": -128,
+        "assigned": 44,
+        "next_letter": "b",
+    },
+}
+
+failed = False
+for pattern_input, returns in RETURNS.items():
+    pattern_output = f"{out_dir}/{pattern_input.split('.')[0]}.py"
+    try:
+        translate_file(f"{in_dir}/{pattern_input}", pattern_output)
+        module = runpy.run_path(pattern_output)
+    # Panics of the translator are BaseExceptions
+    except BaseException as error:
+        failed = True
+        print(f"test {pattern_input} {red}failed{clear} ({type(error).__name__}: {error})")
+        continue
+
+    for function, expected in returns.items():
+        returned = module[function]()
+        if returned == expected and type(returned) == type(expected):
+            print(f"test {pattern_input} {function} {green}passed{clear}")
+        else:
+            failed = True
+            print(f"test {pattern_input} {function} {red}failed{clear} (returned {returned!r}, expected {expected!r})")
+
+if failed:
+    exit(1)
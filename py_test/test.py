@@ -31,21 +31,47 @@ if not os.path.exists(out_dir):
     if env == Environment.OUTER:
         os.mkdir("py_test/pattern_outputs")
 
-for pattern_input in pattern_inputs:
-    pattern_output = pattern_input.split(".")[0] + ".py"
-    try:
-        translate_file(in_path(pattern_input), out_path(pattern_output))
-    except:
-        pass
+if env == Environment.INNER:
+    expected_path = lambda x: f"expected_pattern_outputs/{x}"
+    diagnostics_path = lambda x: f"expected_diagnostics/{x}"
+if env == Environment.OUTER:
+    expected_path = lambda x: f"py_test/expected_pattern_outputs/{x}"
+    diagnostics_path = lambda x: f"py_test/expected_diagnostics/{x}"
 
 red = "\x1B[31m"
 green = "\x1B[32m"
 clear = "\x1B[0m"
 
-for file in os.listdir("py_test/pattern_outputs"):
-    with open(f"py_test/pattern_outputs/{file}", "r") as created_file:
-        with open(f"py_test/expected_pattern_outputs/{file}", "r") as expected_file:
-            if created_file == expected_file:
-                print(f"test {file} {green}passed{clear}")
+failed = False
+for pattern_input in sorted(pattern_inputs):
+    pattern_output = pattern_input.split(".")[0] + ".py"
+    try:
+        diagnostics = translate_file(in_path(pattern_input), out_path(pattern_output))
+    # Panics of the translator are BaseExceptions
+    except BaseException as error:
+        failed = True
+        print(f"test {pattern_output} {red}failed{clear} ({type(error).__name__}: {error})")
+        continue
+
+    # What's reported, one "start end message" per line. Patterns without an expected file report nothing
+    reported = "".join(f"{start} {end} {message}\n" for start, end, message in diagnostics)
+    expected_diagnostics = diagnostics_path(pattern_input.split(".")[0] + ".txt")
+    if os.path.exists(expected_diagnostics):
+        with open(expected_diagnostics, "r") as expected_file:
+            expected_reported = expected_file.read()
+    else:
+        expected_reported = ""
+
+    with open(out_path(pattern_output), "r") as created_file:
+        with open(expected_path(pattern_output), "r") as expected_file:
+            if created_file.read() != expected_file.read():
+                failed = True
+                print(f"test {pattern_output} {red}failed{clear}")
+            elif reported != expected_reported:
+                failed = True
+                print(f"test {pattern_output} {red}failed{clear} (reported {reported!r})")
             else:
-                print(f"test {file} {red}failed{clear}")
+                print(f"test {pattern_output} {green}passed{clear}")
+
+if failed:
+    exit(1)
@@ -1,6 +1,7 @@
 from __future__ import annotations
+import enum
 import struct
-from typing import Type, TypeVar, Union
+from typing import Callable, Iterable, Type, TypeVar, Union
 
 struct_names = [
     "u8", "u16", "u24", "u32", "u48", "u64", "u96", "u128",
@@ -17,14 +18,54 @@ def le_to_int(byts: bytes):
     return value
 
 class Dollar:
-    def __init__(self, offset: int, byts: bytes):
+    def __init__(self, offset: int, byts: bytes, bit: int=0):
         self.offset = int(offset)
         self.byts = byts
+        # Bits of the byte at offset that bitfields already read
+        self.bit = bit
     
     def read(self, amount: int) -> bytes:
+        self.align()
         read_bytes = self.byts[self.offset:self.offset+amount]
         self.offset += amount
         return read_bytes
+
+    def read_bits(self, bits: int, msb_first: bool=False) -> int:
+        """
+        Reads the next bits, for bitfields.
+
+        Args:
+            bits (int): Amount of bits.
+            msb_first (bool, optional): Whether bits are taken from the most significant bit of each byte down,
+                so the first bit read is the most significant bit of the result. Defaults to False.
+        """
+        value = 0
+        for i in range(bits):
+            byte = self.byts[self.offset]
+            if msb_first:
+                value = (value << 1) | ((byte >> (7 - self.bit)) & 1)
+            else:
+                value |= ((byte >> self.bit) & 1) << i
+            self.skip_bits(1)
+        return value
+
+    def skip_bits(self, bits: int):
+        self.bit += bits
+        self.offset += self.bit // 8
+        self.bit %= 8
+
+    def align(self):
+        """
+        Moves to the next byte if a bitfield left the current one partially read.
+        """
+        if self.bit != 0:
+            self.offset += 1
+            self.bit = 0
+
+    def aligned(self) -> Dollar:
+        aligned = self.copy()
+        aligned.align()
+        return aligned
     
     def read_unsigned(self, amount: int) -> u128:
         new_dollar = self.copy()
@@ -37,7 +78,7 @@ class Dollar:
         return self.offset >= len(self.byts) 
     
     def copy(self):
-        return Dollar(self.offset, self.byts)
+        return Dollar(self.offset, self.byts, self.bit)
 
     def __index__(self):
         return self.offset.__index__()
@@ -260,15 +301,144 @@ class Dollar:
         return self.offset.__ceil__()
 
 class Struct:
+    __hexpat_attributes__: dict = {}
+    ___parent_____: Struct | None = None
+    ___big__endian_____: bool = False
+
     def __init__(self, name: str=""):
         self.____name________ = name
         self.___breaked___ = False
-    
+        self.___continued___ = False
+
+    def set_attributes(self, attributes: dict) -> Struct:
+        """
+        Records the hexpat attributes ([[...]]) of this instance.
+        [[name]] changes the name, [[comment]] becomes the docstring and [[hidden]] leaves it out of dump()
+
+        Args:
+            attributes (dict): attribute name -> argument(s). Attributes without arguments have True as their value.
+        """
+        self.__hexpat_attributes__ = attributes
+        if "name" in attributes:
+            self.____name________ = str(attributes["name"])
+        if "comment" in attributes:
+            self.__doc__ = str(attributes["comment"])
+        if "transform" in attributes:
+            self.___transformed_____ = attributes["transform"](self)
+        return self
+
+    def attributes(self) -> dict:
+        return self.__hexpat_attributes__
+
+    def formatted(self) -> str | None:
+        """
+        Result of the [[format]] (or [[format_read]]) function of this instance. None if it has none.
+        """
+        formatter = self.__hexpat_attributes__.get("format", self.__hexpat_attributes__.get("format_read"))
+        if formatter is None:
+            return None
+        return str(formatter(self))
+
+    def transformed(self):
+        """
+        Result of the [[transform]] function of this instance. The instance itself if it has none.
+        """
+        if "transform" in self.__hexpat_attributes__:
+            return self.___transformed_____
+        return self
+
+    def __str__(self) -> str:
+        formatted = self.formatted()
+        if formatted is not None:
+            return formatted
+        return object.__str__(self)
+
+    def __format__(self, format_spec) -> str:
+        formatted = self.formatted()
+        if formatted is not None:
+            return formatted.__format__(format_spec)
+        return object.__format__(self, format_spec)
+
+    def hidden(self) -> bool:
+        return "hidden" in self.__hexpat_attributes__
+
+    def __setattr__(self, name: str, value):
+        # A field defined again replaces the attribute, but both patterns were read, so both are kept as entries.
+        # Assigning a pattern that already is an entry doesn't make a new one
+        if isinstance(value, Struct) and name not in Struct.___internal_____ and not any(value is entry for (_, entry) in self.entries()):
+            self.__dict__.setdefault("___entries______", []).append((name, value))
+        super().__setattr__(name, value)
+
+    ___internal_____ = ("___parent_____", "___length__", "___transformed_____")
+
+    def fields(self) -> dict[str, Struct]:
+        return {name: field for (name, field) in vars(self).items() if isinstance(field, Struct) and name not in Struct.___internal_____}
+
+    def entries(self) -> list[tuple[str, Struct]]:
+        """
+        Every pattern read into this instance, in order. Unlike fields(), patterns whose name was defined again are included.
+        """
+        return self.__dict__.get("___entries______", [])
+
+    def dump(self, indent_lvl: int=0) -> str:
+        """
+        Human readable tree of the fields of this instance. [[hidden]] fields are left out.
+        """
+        lines = []
+        indent = "    " * indent_lvl
+        for (name, field) in self.entries():
+            if field.hidden():
+                continue
+            if field.name() != "":
+                name = field.name()
+            if field.entries():
+                lines.append(f"{indent}{name}:")
+                lines.append(field.dump(indent_lvl+1))
+            else:
+                lines.append(f"{indent}{name} = {field}")
+        return "\n".join(lines)
+
     def init_struct(self, starting_offset: Dollar, end_offset: Dollar):
         self.__address____ = starting_offset.copy()
         self.___dollar______ = end_offset.copy()
         self.__size_______ = end_offset.copy() - self.__address____
     
+    def init_union(self, starting_offset: Dollar):
+        """
+        init_struct for unions. Every field starts at starting_offset, so the union ends where its biggest field does.
+        """
+        end_offset = starting_offset.copy()
+        for (_, field) in self.entries():
+            if field.dollar() > end_offset:
+                end_offset = field.dollar().copy()
+        self.init_struct(starting_offset, end_offset)
+
+    def set_big_endian(self) -> Struct:
+        """
+        Marks this instance as big endian (be). Used by bitfields.
+        """
+        self.___big__endian_____ = True
+        return self
+
+    def set_name(self, name: str) -> Struct:
+        """
+        Sets the name of this instance. Used for fields whose hexpat name isn't a valid python name, so "class" is read into self.class_
+        """
+        self.____name________ = name
+        return self
+
+    def set_parent(self, parent: Struct) -> Struct:
+        """
+        Sets the struct this instance is a field of, which hexpat's "parent" refers to. Must be done before reading it.
+        """
+        self.___parent_____ = parent
+        return self
+
+    def parent(self) -> Struct:
+        if self.___parent_____ is None:
+            raise Exception(f'"{self.name()}" has no parent')
+        return self.___parent_____
+
     def name(self) -> str:
         return self.____name________
     
@@ -278,6 +448,12 @@ class Struct:
     def break_(self):
         self.___breaked___ = True
 
+    def continue_(self):
+        """
+        hexpat's "continue" outside of loops. The array this instance is read into leaves it out.
+        """
+        self.___continued___ = True
+
     def dollar(self) -> Dollar:
         return self.___dollar______
     
@@ -302,9 +478,15 @@ class IntStruct(Struct):
         return self.___value_____.__repr__()
     
     def __str__(self) -> str:
+        formatted = self.formatted()
+        if formatted is not None:
+            return formatted
         return self.___value_____.__str__()
 
     def __format__(self, format_spec) -> str:
+        formatted = self.formatted()
+        if formatted is not None:
+            return formatted.__format__(format_spec)
         return self.___value_____.__format__(format_spec)
 
     def __lt__(self, other) -> bool:
@@ -706,9 +888,15 @@ class RealNum(Struct):
         return self.___value_______.__repr__()
     
     def __str__(self):
+        formatted = self.formatted()
+        if formatted is not None:
+            return formatted
         return self.___value_______.__str__()
 
     def __format__(self, format_spec):
+        formatted = self.formatted()
+        if formatted is not None:
+            return formatted.__format__(format_spec)
         return self.___value_______.__format__(format_spec)
 
 class Float(RealNum):
@@ -746,11 +934,39 @@ class Character(Struct):
         return self.___value_____.__repr__()
     
     def __str__(self):
+        formatted = self.formatted()
+        if formatted is not None:
+            return formatted
         return self.___value_____.__str__()
 
     def __format__(self, format_spec):
+        formatted = self.formatted()
+        if formatted is not None:
+            return formatted.__format__(format_spec)
         return self.___value_____.__format__(format_spec)
 
+    def __eq__(self, other) -> bool:
+        return self.___value_____.__eq__(str(other))
+
+    def __ne__(self, other) -> bool:
+        return self.___value_____.__ne__(str(other))
+
+    def __hash__(self) -> int:
+        return self.___value_____.__hash__()
+
+    def __add__(self, other) -> str | int:
+        """
+        Strings and characters are concatenated. Numbers are added to the code of this character
+        """
+        if isinstance(other, (str, Character)):
+            return self.___value_____.__add__(str(other))
+        return ord(self.___value_____) + int(other)
+
+    def __radd__(self, other) -> str | int:
+        if isinstance(other, (str, Character)):
+            return str(other).__add__(self.___value_____)
+        return int(other) + ord(self.___value_____)
+
 class char(Character):
     def __init__(self, value: str="\0", name: str=""):
         super().__init__(1, value, name)
@@ -800,9 +1016,15 @@ class Bool(Struct):
         return self.___value_____.__repr__()
     
     def __str__(self):
+        formatted = self.formatted()
+        if formatted is not None:
+            return formatted
         return self.___value_____.__str__()
 
     def __format__(self, format_spec):
+        formatted = self.formatted()
+        if formatted is not None:
+            return formatted.__format__(format_spec)
         return self.___value_____.__format__(format_spec)
 
 class Padding(Struct):
@@ -831,33 +1053,136 @@ class Padding(Struct):
         return f"padding[{self.___length______}]: {self.value()}"
     
     def __str__(self):
+        formatted = self.formatted()
+        if formatted is not None:
+            return formatted
         return f"padding[{self.___length______}]"
 
     def __format__(self, format_spec):
+        formatted = self.formatted()
+        if formatted is not None:
+            return formatted.__format__(format_spec)
         return f"padding[{self.___length______}]".__format__(format_spec)
 
+class BitFieldEntry(IntStruct):
+    def __init__(self, value: int=0, bits: int=0, name: str=""):
+        """
+        Value of a bitfield entry
+
+        Args:
+            value (int, optional): Defaults to 0.
+            bits (int, optional): Length of the entry in bits. Defaults to 0.
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+        self.___value_____ = value
+        self.___bits_____ = bits
+        super().__init__(name)
+
+    def bits(self) -> int:
+        return self.___bits_____
+
 class BitField(Struct):
-    _bit_field___masks_dict = {
-        1: 0b00000001,
-        2: 0b00000011,
-        3: 0b00000111,
-        4: 0b00001111,
-        5: 0b00011111,
-        6: 0b00111111,
-        7: 0b01111111,
-        8: 0b11111111,
-    }
+    # [[bitfield_order(order, size)]]. None when the bitfield has no such attribute
+    _bit_field___msb_first: bool | None = None
+    _bit_field___size: int | None = None
+
     def __init__(self, name: str=""):
         super().__init__(name)
+        self.___bits__read_____ = 0
+
+    def msb_first(self) -> bool:
+        """
+        Whether entries start at the most significant bit. By default, big endian bitfields do and little endian ones don't.
+        """
+        if self._bit_field___msb_first is None:
+            return self.___big__endian_____
+        return self._bit_field___msb_first
+
+    def read_bits(self, dollar: Dollar, bits: int, type_: Type[Struct]|None=None, signed: bool=False):
+        """
+        Reads the next entry of this bitfield, starting at the bit dollar is at.
+
+        Args:
+            dollar (Dollar): Current offset. It's moved past the bits read.
+            bits (int): Length of the entry in bits.
+            type_ (Type[Struct] | None, optional): Type of the entry (bool, an enum...). Defaults to None.
+            signed (bool, optional): Whether the entry is a signed number. Defaults to False.
+        """
+        bits = int(bits)
+        if self.___bits__read_____ == 0:
+            self.___start_____ = dollar.copy()
+        entry_start = dollar.copy()
+        if self._bit_field___size is not None and self.msb_first() != self.___big__endian_____:
+            # The order doesn't match the endianness, so the entries are taken from the whole bitfield as one number
+            container_end = self.___start_____.copy()
+            container_end.skip_bits(self._bit_field___size)
+            container_bytes = self.___start_____.byts[self.___start_____.offset:container_end.aligned().offset]
+            container = int.from_bytes(container_bytes, "big" if self.___big__endian_____ else "little")
+            if self.msb_first():
+                shift = self._bit_field___size - self.___bits__read_____ - bits
+            else:
+                shift = self.___bits__read_____
+            value = (container >> shift) & ((1 << bits) - 1)
+            dollar.skip_bits(bits)
+        else:
+            value = dollar.read_bits(bits, self.msb_first())
+        self.___bits__read_____ += bits
+        if signed and bits > 0 and value & (1 << (bits - 1)):
+            value -= 1 << bits
+
+        if type_ is None:
+            entry = BitFieldEntry(value, bits)
+        elif issubclass(type_, Bool):
+            entry = Bool(bool(value))
+        else:
+            entry = type_(value)
+        # Entries start and end at the bytes they are in
+        entry.init_struct(entry_start, dollar.aligned())
+        return entry
+
+    def init_bitfield(self, starting_offset: Dollar, dollar: Dollar):
+        """
+        init_struct for bitfields. $ stays at the bit after the last one read, so the next bitfield continues there.
+        Bitfields with a [[bitfield_order]] size always end after that many bits.
+        """
+        if self._bit_field___size is not None:
+            dollar.offset = starting_offset.offset
+            dollar.bit = starting_offset.bit
+            dollar.skip_bits(self._bit_field___size)
+        self.init_struct(starting_offset, dollar.aligned())
 
 T = TypeVar('T', bound=Struct)
 class Array(list[T], Struct):
-    def __init__(self, type_: Type[T], length: int | str, name: str="") -> None:
+    def __init__(self, type_: Type[T], length: int | Callable[[Dollar], bool] | None, name: str="") -> None:
+        """
+        Args:
+            type_ (Type[T]): The type of the entries.
+            length (int | Callable[[Dollar], bool] | None): Amount of entries.
+                A function is called with the current offset before reading each entry, and the array ends when it returns False (u8 a[while($ < 100)]).
+                None reads until an entry that is all zeroes, which is included (u8 a[]).
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
         self.___type_____ = type_
         self.___length__ = length
         list.__init__(self)
         Struct.__init__(self, name)
     
+    def new_entry(self) -> T:
+        entry = self.___type_____()
+        if self.___parent_____ is not None:
+            # Entries are fields of the struct the array is in
+            entry.set_parent(self.___parent_____)
+        return entry
+
+    def read_entry(self, other: Dollar) -> bool:
+        """
+        Reads the next entry at other. Entries that used "continue" are left out. Returns whether the entry used "break".
+        """
+        entry = self.new_entry() @ other
+        if not entry.___continued___:
+            self.append(entry)
+        return entry.___breaked___
+
     def __matmul__(self, other):
         if not (isinstance(other, Dollar) or isinstance(other, IntStruct)):
             raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {type(other)} was used instead')
@@ -865,33 +1190,177 @@ class Array(list[T], Struct):
             other = other.to_dollar()
         other_copy = other.copy()
         self.clear()
-        if isinstance(self.___length__, int) or isinstance(self.___length__, IntStruct):
-            for _ in range(0, self.___length__):
-                self.append(self.___type_____() @ other)
-                if self[-1].___breaked___ or other.eof():
+        if self.___length__ is None:
+            while not other.eof():
+                entry_start = other.copy()
+                if self.read_entry(other) or not any(other.byts[entry_start.offset:other.offset]):
+                    break
+        elif callable(self.___length__):
+            while self.___length__(other):
+                if self.read_entry(other) or other.eof():
                     break
-        elif "while" in self.___length__:
-            bool_statement = self.___length__.split("while(")[1].split(")")[:-1]
-            bool_statement = ")".join(bool_statement)
-            bool_statement = bool_statement.replace("_dollar___offset", "other")
-            while eval(bool_statement):
-                self.append(self.___type_____() @ other)
-                if self[-1].___breaked___ or other.eof():
+        elif isinstance(self.___length__, int) or isinstance(self.___length__, IntStruct):
+            for _ in range(0, self.___length__):
+                if self.read_entry(other) or other.eof():
                     break
         else:
-            raise Exception(f"Array lengths other than int or while statements are not supported. Received length: {self.___length__}")
+            raise Exception(f"Array lengths must be an int, a function or None. Received length: {self.___length__}")
         Struct.init_struct(self, other_copy, other.copy())
         return self
 
+    def zeroed(self) -> Array[T]:
+        """
+        Fills the array with default entries instead of reading them. Used for local variables.
+        """
+        self.clear()
+        if isinstance(self.___length__, int) or isinstance(self.___length__, IntStruct):
+            for _ in range(0, self.___length__):
+                self.append(self.___type_____())
+        return self
+
+class String(Struct):
+    def __init__(self, type_: Type[Character]=char, length: int|None=None, value: str="", name: str=""):
+        """
+        char/char16 array, read as a python string
+
+        Args:
+            type_ (Type[Character], optional): char or char16. Defaults to char.
+            length (int | None, optional): Amount of characters. None reads until a null character. Defaults to None.
+            value (str, optional): Defaults to "".
+            name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
+        """
+        self.___type_____ = type_
+        self.___length__ = length
+        self.___value_____ = value
+        super().__init__(name)
+
+    def length(self) -> int:
+        return len(self.___value_____)
+
+    def value(self) -> str:
+        return self.___value_____
+
+    def __matmul__(self, other):
+        if not (isinstance(other, Dollar) or isinstance(other, IntStruct)):
+            raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {type(other)} was used instead')
+        if isinstance(other, IntStruct):
+            other = other.to_dollar()
+        starting_offset = other.copy()
+        characters = []
+        if self.___length__ is None:
+            while not other.eof():
+                character = (self.___type_____() @ other).value()
+                if character.strip("\0") == "":
+                    break
+                characters.append(character)
+        else:
+            for _ in range(0, int(self.___length__)):
+                characters.append((self.___type_____() @ other).value())
+        self.___value_____ = "".join(characters)
+        super().init_struct(starting_offset, other.copy())
+        return self
+
+    def zeroed(self) -> String:
+        """
+        Fills the string with null characters instead of reading them. Used for local variables.
+        """
+        if self.___length__ is None:
+            self.___value_____ = ""
+        else:
+            self.___value_____ = self.___type_____().value() * int(self.___length__)
+        return self
+
+    def __repr__(self):
+        return self.___value_____.__repr__()
+
+    def __str__(self):
+        formatted = self.formatted()
+        if formatted is not None:
+            return formatted
+        return self.___value_____.__str__()
+
+    def __format__(self, format_spec):
+        formatted = self.formatted()
+        if formatted is not None:
+            return formatted.__format__(format_spec)
+        return self.___value_____.__format__(format_spec)
+
+    def __len__(self):
+        return self.___value_____.__len__()
+
+    def __getitem__(self, key):
+        return self.___value_____.__getitem__(key)
+
+    def __contains__(self, other):
+        return self.___value_____.__contains__(str(other))
+
+    def __lt__(self, other) -> bool:
+        return self.___value_____.__lt__(str(other))
+
+    def __le__(self, other) -> bool:
+        return self.___value_____.__le__(str(other))
+
+    def __eq__(self, other) -> bool:
+        return self.___value_____.__eq__(str(other))
+
+    def __ne__(self, other) -> bool:
+        return self.___value_____.__ne__(str(other))
+
+    def __gt__(self, other) -> bool:
+        return self.___value_____.__gt__(str(other))
+
+    def __ge__(self, other) -> bool:
+        return self.___value_____.__ge__(str(other))
+
+    def __hash__(self) -> int:
+        return self.___value_____.__hash__()
+
+    def __add__(self, other) -> str:
+        return self.___value_____.__add__(str(other))
+
+    def __radd__(self, other) -> str:
+        return str(other).__add__(self.___value_____)
+
 class EnumException(Exception):
     def __init__(self, *args: object) -> None:
         super().__init__(*args)
 
 V = TypeVar('V', bound=IntStruct|RealNum|Character|Bool)
 
+def enum_member_name(entry: str, entries: Iterable[str]) -> str:
+    """
+    Name the member of Members for the entry has. The standard library doesn't take some names, like _A_ or mro,
+    so they get an "m" in front until they are a name it takes that isn't another entry
+    """
+    name = entry
+    while (name.startswith("_") and name.endswith("_")) or name == "mro" or (name != entry and name in entries):
+        name = "m" + name
+    return name
+
 class Enum(UnsignedLe):
     __lengthed__types_____ = [type(UnsignedLe), type(SignedLe), type(RealNum), type(Character), type(Bool)]
+    _enum__dict___: dict = {}
+    _enum__ranges___: list = []
+    _enum__flags___: bool = False
+    _enum__python__names___: dict = {}
+    Members: Type[enum.IntEnum]
+
+    def __init_subclass__(cls, **kwargs):
+        """
+        Gives every translated enum a standard library enum with the same entries, Kind.Members.
+        The entries of the translated enum become its members, so Kind.Image is Kind.Members.Image
+        """
+        super().__init_subclass__(**kwargs)
+        base = enum.IntFlag if cls._enum__flags___ else enum.IntEnum
+        entries = cls._enum__dict___.values()
+        cls.Members = base(cls.__name__, [(enum_member_name(entry, entries), value) for (value, entry) in cls._enum__dict___.items()])
+        for entry in entries:
+            # Entries like "None" are class attributes with a python name, None_
+            setattr(cls, cls._enum__python__names___.get(entry, entry), cls.Members[enum_member_name(entry, entries)])
+
     def __init__(self, type_: Type[V]|int, value: V=None, name: str=""):
+        if value is None:
+            value = 0
         if isinstance(type_, int):
             byte_amount = type_
         elif type(type_) in self.__lengthed__types_____:
@@ -909,19 +1378,84 @@ class Enum(UnsignedLe):
             raise EnumException(errormsg)
         super().__init__(byte_amount, value, name)
 
+    def entry(self) -> str | None:
+        """
+        Name of the entry this value is, or of the range (A = 0 ... 9) it's in. None if it's neither.
+        """
+        if self.value() in self._enum__dict___:
+            return self._enum__dict___[self.value()]
+        for (start, end, entry) in self._enum__ranges___:
+            if start <= self.value() <= end:
+                return entry
+        if self._enum__flags___ and self.value() != 0:
+            # Combination of flags, Read|Execute
+            entries = self._enum__dict___.values()
+            hexpat_names = {enum_member_name(entry, entries): entry for entry in entries}
+            return "|".join(hexpat_names.get(name, name) for name in self.member().name.split("|"))
+        return None
+
+    def member(self) -> enum.IntEnum | None:
+        """
+        This value as a member of Members. None if it isn't one of the entries.
+        """
+        try:
+            return self.Members(self.value())
+        except ValueError:
+            return None
+
     def name(self) -> str:
+        entry = self.entry()
+        if entry is None:
+            entry = "???"
         if super().name() != "":
-            name = super().name()
-            if self.value() in self._enum__dict___:
-                name = f"{name}: {self._enum__dict___[self.value()]}"
-            else:
-                name = f"{name}: ???"
-        else:
-            if self.value() in self._enum__dict___:
-                name = f"{self._enum__dict___[self.value()]}"
-            else:
-                name = f"???"
-        return name
+            return f"{super().name()}: {entry}"
+        return entry
+
+class Namespace:
+    """
+    Base of every translated hexpat namespace. Its members are class attributes, so my::Header is my.Header
+    """
+    pass
+
+def namespace(scope: dict, name: str) -> Type[Namespace]:
+    """
+    Base class for a namespace being (re)opened. Reopening a namespace keeps the members it already had.
+
+    Args:
+        scope (dict): The scope the namespace is defined in (`locals()`).
+        name (str): The name of the namespace.
+    """
+    previous = scope.get(name)
+    if isinstance(previous, type) and issubclass(previous, Namespace):
+        return previous
+    return Namespace
+
+def hexpat_div(dividend, divisor):
+    """
+    hexpat's "/". Integers are divided like in C, truncating towards zero. If one of them is a float, it's a true division.
+    """
+    if isinstance(dividend, (float, RealNum)) or isinstance(divisor, (float, RealNum)):
+        return float(dividend) / float(divisor)
+    quotient = abs(int(dividend)) // abs(int(divisor))
+    if (int(dividend) < 0) != (int(divisor) < 0):
+        return -quotient
+    return quotient
+
+def hexpat_wrap(value, bits, signed):
+    """
+    Wraps value around like an integer of the given width would
+    """
+    value = int(value) & ((1 << bits) - 1)
+    if signed and value >> (bits - 1):
+        return value - (1 << bits)
+    return value
+
+def placed(placed_patterns: list[tuple[str, Struct]], name: str, pattern: Struct) -> Struct:
+    """
+    Records a pattern placed outside of structs in placed_patterns, the list of the translated module
+    """
+    placed_patterns.append((name, pattern))
+    return pattern
 
 def sizeof(struct: Struct) -> int:
     return struct.size()
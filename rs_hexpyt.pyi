@@ -1,8 +1,11 @@
-from typing import List
+from typing import List, Tuple
 
 def translate_file(
     input_file_path: str,
     output_file_path: str,
     indentation: str="    ",
     extra_paths: List[str]=[]
-): ...
+) -> List[Tuple[int, int, str]]:
+    """
+    Translates the pattern at input_file_path to python, and returns its type errors as (start, end, message)
+    """
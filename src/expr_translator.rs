@@ -1,20 +1,27 @@
+use std::{cell::RefCell, collections::{HashMap, HashSet}};
+
 use hexparser::{Expr, m_parser::{Statement, FuncCall, Definition, HexType}, token::{Spanned, ValueType}};
 
-use crate::{PyLines, one_py_line, PyLine, unkown_py_lines};
+use crate::{PyLines, one_py_line, PyLine, unkown_py_lines, type_inference::Diagnostic};
+
+use self::identifiers::{mangle, mangle_field, mangle_entry, generated, DOLLAR};
+use self::tokens::{set_tokens, tokens_between, is_spelled};
 
-use self::translators::{translate_value, translate_expr_list, translate_unary, translate_binary, translate_ternary, translate_call, translate_if, translate_if_block, translate_definition, translate_array_definition, translate_bitfield_entry, translate_enum_entry, translate_namespace_access, translate_using, translate_return, translate_func, translate_struct, translate_namespace, translate_enum, translate_bitfield, translate_access, translate_array_access, translate_attribute, translate_attribute_argument, translate_while_loop, translate_for_loop, translate_cast, translate_union, translate_match, translate_try_catch, translate_assignment, translate_while_loop_statement, translate_hextypedef};
+use self::translators::{translate_value, translate_expr_list, translate_unary, translate_binary, translate_ternary, translate_call, translate_if, translate_if_block, translate_definition, translate_array_definition, translate_bitfield_entry, translate_enum_entry, translate_namespace_access, translate_using, translate_return, translate_func, translate_struct, translate_namespace, translate_enum, translate_bitfield, translate_access, translate_array_access, translate_attribute, translate_attribute_argument, translate_while_loop, translate_for_loop, translate_cast, translate_union, translate_match, translate_try_catch, translate_assignment, translate_while_loop_statement, translate_hextypedef, translate_break, translate_continue};
 
 mod translators;
+pub(crate) mod identifiers;
+pub(crate) mod tokens;
+pub(crate) mod literals;
 
-fn translate_expr(expr: Expr, lvl: usize, context: StatementsContext) -> PyLines {
+fn translate_expr(expr: Spanned<Expr>, lvl: usize, context: &Scope) -> PyLines {
+    let (expr, span) = expr;
     match expr {
         Expr::Error => one_py_line(lvl, "raise Error".to_string()),
-        Expr::Value { val } => translate_value(val, lvl, context),
+        Expr::Value { val } => translate_value(val, (span.start, span.end), lvl, context),
         Expr::ExprList { list } => translate_expr_list(list, lvl, context),
         Expr::UnnamedParameter { type_ } => PyLines::One(translate_hextype(type_.0, lvl, context)),
-        Expr::Local { name } => {
-            one_py_line(lvl, name.0)
-        },
+        Expr::Local { name } => one_py_line(lvl, resolve_local(name.0, context)),
         Expr::Unary { operation, operand } => translate_unary(operation, operand, lvl, context),
         Expr::Binary { loperand, operator, roperand } => translate_binary(loperand, operator, roperand, lvl, context),
         Expr::Ternary { loperand, moperand, roperand } => translate_ternary(loperand, moperand, roperand, lvl, context),
@@ -32,7 +39,8 @@ fn translate_expr(expr: Expr, lvl: usize, context: StatementsContext) -> PyLines
     }
 }
 
-fn translate_statement(stmnt: Statement, lvl: usize, context: StatementsContext) -> PyLines {
+fn translate_statement(stmnt: Spanned<Statement>, lvl: usize, context: &Scope) -> PyLines {
+    let (stmnt, span) = stmnt;
     match stmnt {
         Statement::Call(FuncCall { func_name, arguments }) => translate_call(func_name, arguments, lvl, context),
         Statement::If { test, consequent } => translate_if(test, consequent, lvl, context),
@@ -40,14 +48,9 @@ fn translate_statement(stmnt: Statement, lvl: usize, context: StatementsContext)
         Statement::ArrayDefinition { value_type, array_name, size, body } => translate_array_definition(value_type, array_name, size, body, lvl, context),
         Statement::Using { new_name, template_parameters, old_name } => translate_using(new_name, template_parameters, old_name, lvl, context),
         Statement::Return { value } => translate_return(value, lvl, context),
-        Statement::Continue => {
-            panic!("Didn't take context into account");
-            one_py_line(lvl, "continue".to_string())
-        },
-        Statement::Break => {
-            panic!("Didn't take context into account");
-            one_py_line(lvl, "break".to_string())
-        },
+        // Reported at the keyword
+        Statement::Continue => translate_continue((span.start, span.start + "continue".len()), lvl, context),
+        Statement::Break => translate_break((span.start, span.start + "break".len()), lvl, context),
         Statement::Func { name, args, body } => translate_func(name, args, body, lvl, context),
         Statement::Struct { name, body, template_parameters } => translate_struct(name, body, template_parameters, lvl, context),
         Statement::Namespace { name, body } => translate_namespace(name, body, lvl, context),
@@ -59,16 +62,154 @@ fn translate_statement(stmnt: Statement, lvl: usize, context: StatementsContext)
         Statement::TryCatch { try_block, catch_block } => translate_try_catch(try_block, catch_block, lvl, context),
         Statement::Definition(Definition { value_type, name, body }) => translate_definition(value_type, name, body, lvl, context),
         Statement::Assignment { loperand, operator, roperand } => translate_assignment(loperand, operator, roperand, lvl, context),
-        Statement::Error => {
-            panic!("Didn't take context into account");
-            one_py_line(lvl, "raise Error".to_string())
-        },
+        Statement::Error => one_py_line(lvl, "raise Error".to_string()),
         Statement::WhileLoop { condition, body } => translate_while_loop_statement(condition, body, lvl, context),
-        Statement::Padding { padding_body } => translate_expr(padding_body.0, lvl, context),
-        Statement::BitFieldEntry { name, length } => unreachable!(), // bitfield statements are handled separately
+        Statement::Padding { padding_body } => translate_expr(*padding_body, lvl, context),
+        Statement::BitFieldEntry { name, length } => translate_bitfield_entry(name, length, (span.start, span.end), lvl, context),
+    }
+}
+
+/// Keeps the tokens of the file being translated, for what the AST doesn't keep
+pub(crate) fn set_source(source: &str) {
+    set_tokens(source);
+}
+
+thread_local! {
+    /// What couldn't be translated, reported like the type errors
+    static UNSUPPORTED: RefCell<Vec<Diagnostic>> = RefCell::new(Vec::new());
+}
+
+/// Translation of something that rs_hexpyt can't do. It's reported, and the python raises if it gets there
+pub(crate) fn unsupported(span: (usize, usize), message: &str, lvl: usize) -> PyLines {
+    report(span, message);
+    one_py_line(lvl, format!("raise NotImplementedError({message:?})"))
+}
+
+/// Reports something that rs_hexpyt translates differently than ImHex reads it
+pub(crate) fn report((start, end): (usize, usize), message: &str) {
+    UNSUPPORTED.with(|u| u.borrow_mut().push(Diagnostic { start, end, message: message.to_string() }));
+}
+
+/// What was reported by `unsupported` since the last call
+pub(crate) fn take_unsupported() -> Vec<Diagnostic> {
+    UNSUPPORTED.with(|u| u.take())
+}
+
+thread_local! {
+    /// Why the file being translated was refused, if it was
+    static REFUSED: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// Refuses the file being translated, because its translation would run code from it. Only the first reason is kept
+pub(crate) fn refuse(reason: String) {
+    REFUSED.with(|r| {
+        r.borrow_mut().get_or_insert(reason);
+    })
+}
+
+/// Why the file was refused since the last call, if it was
+pub(crate) fn take_refused() -> Option<String> {
+    REFUSED.with(|r| r.take())
+}
+
+/// Whether a definition with a body is `u8 a = 5;` and not `u8 a @ 5;`. The parser keeps the same body for both,
+/// so it's told by the token after the name
+pub(crate) fn is_initialization(name: &Spanned<Expr>, body_start: usize) -> bool {
+    match &name.0 {
+        Expr::Local { name } if is_spelled(name.1.start, name.1.end, &name.0) => tokens_between(name.1.end, body_start).first()
+            .map_or(false, |token| token.is("=")),
+        // Definitions from included files can't be told apart. Those are mostly the locals of functions
+        _ => true
+    }
+}
+
+/// Structs a struct inherits and where they are written, `struct B : A, ns::C<4> {` -> `["A", "ns::C"]`. The parser doesn't keep them, so they are
+/// read from the tokens between the name and the body. Template arguments are left out
+pub(crate) fn struct_parents(name: &Spanned<String>, body_start: usize) -> Vec<(String, (usize, usize))> {
+    // Structs from included files
+    if !is_spelled(name.1.start, name.1.end, &name.0) {
+        return Vec::new()
+    }
+    let mut parents = Vec::new();
+    let mut parent = String::new();
+    let mut span = (0, 0);
+    let mut in_parents = false;
+    let mut template_depth = 0;
+    for token in tokens_between(name.1.end, body_start) {
+        match token.text.as_str() {
+            "{" => break,
+            "<" => template_depth += 1,
+            ">" => template_depth -= 1,
+            _ if template_depth > 0 => (),
+            ":" if !in_parents => in_parents = true,
+            "," if in_parents => parents.push((std::mem::take(&mut parent), span)),
+            _ if in_parents => {
+                if parent.is_empty() {
+                    span.0 = token.start;
+                }
+                span.1 = token.end;
+                parent.push_str(&token.text)
+            },
+            _ => ()
+        }
+    }
+    if !parent.is_empty() {
+        parents.push((parent, span));
+    }
+
+    parents
+}
+
+/// Python for a name used in an expression
+fn resolve_local(name: String, context: &Scope) -> String {
+    match name.as_str() {
+        "$" => generated(DOLLAR),
+        "this" => "self".to_string(),
+        "parent" => "self.parent()".to_string(),
+        _ => match context.lookup(&name) {
+            Some(Symbol { kind: SymbolKind::Field, .. }) => format!("self.{}", mangle_field(&name)),
+            Some(Symbol { kind: SymbolKind::Entry, .. }) => mangle_entry(&name),
+            _ => resolve_type(&name, context)
+        }
     }
 }
 
+/// Python for the first segment of a type or function name. Inside of functions the names defined in namespaces
+/// need their whole path, because the class bodies namespaces become are out of their reach
+fn resolve_type(name: &str, context: &Scope) -> String {
+    match context.in_function() {
+        true => qualified_type(name, context),
+        false => mangle(name)
+    }
+}
+
+/// Whole python path of the first segment of a type or function name, `Header` -> `my.Header` inside of `namespace my`
+fn qualified_type(name: &str, context: &Scope) -> String {
+    match context.lookup(name) {
+        Some(Symbol { kind: SymbolKind::Type | SymbolKind::Enum, value_type }) => value_type,
+        _ => mangle(name)
+    }
+}
+
+/// Python for a type or function name, each segment resolved like `resolve_type` does with the first one
+pub(crate) fn resolve_path<'a>(mut path: impl Iterator<Item = &'a str>, context: &Scope) -> String {
+    let first = path.next().map(|first| resolve_type(first, context)).unwrap_or_default();
+    std::iter::once(first)
+        .chain(path.map(mangle))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Whole python path of a type, which is the same wherever it's used
+pub(crate) fn qualified_path<'a>(mut path: impl Iterator<Item = &'a str>, context: &Scope) -> String {
+    let first = path.next().map(|first| qualified_type(first, context)).unwrap_or_default();
+    std::iter::once(first)
+        .chain(path.map(mangle))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Kind of construct a scope is
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum StatementsContext {
     None,
@@ -80,33 +221,235 @@ pub(crate) enum StatementsContext {
     ForLoop,
     Union,
     Try,
+    /// The values of the entries of an enum
+    Enum,
 }
 
-pub(crate) fn vec_translate_statements(stmnts: Vec<Spanned<Statement>>, lvl: usize, context: StatementsContext) -> PyLines {
-    let mut lines = Vec::new();
-    for stmnt in stmnts {
-        lines.extend(translate_statement(stmnt.0, lvl, context))
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SymbolKind {
+    /// Read into the struct being translated, `self.{name}`
+    Field,
+    /// A python variable
+    Local,
+    /// An enum, whose entries are attributes of its class
+    Enum,
+    /// A struct, union, bitfield, function, namespace or `using` name
+    Type,
+    /// An entry of the enum being translated, used in the value of a later one
+    Entry,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Symbol {
+    pub(crate) kind: SymbolKind,
+    /// Translated type, e.g. `u8` or `Array[u8]`. For enums and types, their python path, e.g. `my.Header`
+    pub(crate) value_type: String,
+}
+
+/// One of the nested constructs being translated, with the names defined in it.
+/// Each scope points to the one it's in, so the whole stack is known, not only the innermost construct
+pub(crate) struct Scope<'a> {
+    kind: StatementsContext,
+    symbols: RefCell<HashMap<String, Symbol>>,
+    /// Statement a for loop runs after every iteration, so it must run before a `continue` too
+    loop_step: RefCell<Option<String>>,
+    /// Python names of the globals a function assigns, which it has to declare `global`
+    assigned_globals: RefCell<Vec<String>>,
+    /// Fields of the structs defined in this scope, which the structs that inherit them also have
+    struct_fields: RefCell<HashMap<String, HashMap<String, Symbol>>>,
+    /// Python paths of the enums defined in this scope
+    enums: RefCell<HashSet<String>>,
+    /// Python path of the namespace this scope is, `a.b`
+    namespace_path: Option<String>,
+    outer: Option<&'a Scope<'a>>,
+}
+
+impl Scope<'_> {
+    /// The scope of the whole file
+    pub(crate) fn global() -> Scope<'static> {
+        Scope { kind: StatementsContext::None, symbols: RefCell::new(HashMap::new()), loop_step: RefCell::new(None), assigned_globals: RefCell::new(Vec::new()), struct_fields: RefCell::new(HashMap::new()), enums: RefCell::new(HashSet::new()), namespace_path: None, outer: None }
     }
 
-    unkown_py_lines(lines)
+    /// A scope inside this one. `symbols` are the names known before translating it, like the fields of a struct
+    pub(crate) fn push(&self, kind: StatementsContext, symbols: HashMap<String, Symbol>) -> Scope<'_> {
+        Scope { kind, symbols: RefCell::new(symbols), loop_step: RefCell::new(None), assigned_globals: RefCell::new(Vec::new()), struct_fields: RefCell::new(HashMap::new()), enums: RefCell::new(HashSet::new()), namespace_path: None, outer: Some(self) }
+    }
+
+    /// The scope of the body of a namespace, whose python path is `path`
+    pub(crate) fn push_namespace(&self, path: String) -> Scope<'_> {
+        Scope { namespace_path: Some(path), ..self.push(StatementsContext::Namespace, HashMap::new()) }
+    }
+
+    /// Python path of `python_name` if it's defined in this scope, `Header` -> `my.Header` inside of `namespace my`
+    pub(crate) fn qualified(&self, python_name: &str) -> String {
+        match (&self.namespace_path, self.outer) {
+            (Some(path), _) => format!("{path}.{python_name}"),
+            (None, Some(outer)) if !matches!(self.kind, StatementsContext::None | StatementsContext::Namespace) => outer.qualified(python_name),
+            _ => python_name.to_string()
+        }
+    }
+
+    /// Whether this is inside of a function or the `__matmul__` of a type. Those are python functions,
+    /// which don't see the names of the class bodies they are in
+    pub(crate) fn in_function(&self) -> bool {
+        !matches!(self.owner(), StatementsContext::None | StatementsContext::Namespace)
+    }
+
+    /// Kind of the innermost construct
+    pub(crate) fn kind(&self) -> StatementsContext {
+        self.kind
+    }
+
+    /// Kind of the innermost scope that is one of `kinds`
+    pub(crate) fn enclosing(&self, kinds: &[StatementsContext]) -> Option<StatementsContext> {
+        if kinds.contains(&self.kind) {
+            Some(self.kind)
+        } else {
+            self.outer.and_then(|outer| outer.enclosing(kinds))
+        }
+    }
+
+    /// Kind of the construct definitions are made in. Loops and try blocks don't own their definitions,
+    /// a `u8 a;` in a loop in a struct is still a field of the struct
+    pub(crate) fn owner(&self) -> StatementsContext {
+        self.enclosing(&[
+            StatementsContext::None,
+            StatementsContext::Namespace,
+            StatementsContext::Function,
+            StatementsContext::Struct,
+            StatementsContext::Union,
+            StatementsContext::Bitfield,
+        ]).expect("Every scope is inside the global one")
+    }
+
+    pub(crate) fn set_loop_step(&self, step: String) {
+        *self.loop_step.borrow_mut() = Some(step);
+    }
+
+    /// Step of the innermost loop, if it's a for loop
+    pub(crate) fn loop_step(&self) -> Option<String> {
+        match self.kind {
+            StatementsContext::WhileLoop => None,
+            StatementsContext::ForLoop => self.loop_step.borrow().clone(),
+            _ => self.outer.and_then(|outer| outer.loop_step())
+        }
+    }
+
+    /// Records that `name` is assigned. If it's a global assigned in a function, or in the `__matmul__` of a struct,
+    /// union or bitfield, the python function has to declare it
+    pub(crate) fn assign(&self, name: &str) {
+        let owner = self.owner();
+        // In structs, $ is a parameter of __matmul__
+        let is_global = match name {
+            "$" => owner == StatementsContext::Function,
+            _ => self.defined_in(name) == Some(StatementsContext::None)
+        };
+        if !is_global || matches!(owner, StatementsContext::None | StatementsContext::Namespace) {
+            return
+        }
+        let python_name = resolve_local(name.to_string(), self);
+        self.assign_global(python_name);
+    }
+
+    fn assign_global(&self, python_name: String) {
+        match (self.kind, self.outer) {
+            (
+                StatementsContext::Function
+                | StatementsContext::Struct
+                | StatementsContext::Union
+                | StatementsContext::Bitfield,
+                _
+            ) => {
+                let mut assigned = self.assigned_globals.borrow_mut();
+                if !assigned.contains(&python_name) {
+                    assigned.push(python_name);
+                }
+            },
+            (_, Some(outer)) => outer.assign_global(python_name),
+            (_, None) => unreachable!("Only called inside functions")
+        }
+    }
+
+    /// Globals the python function of this scope assigns, in the order they were first assigned
+    pub(crate) fn assigned_globals(&self) -> Vec<String> {
+        self.assigned_globals.borrow().clone()
+    }
+
+    /// Kind of the innermost scope `name` is defined in
+    fn defined_in(&self, name: &str) -> Option<StatementsContext> {
+        match self.symbols.borrow().contains_key(name) {
+            true => Some(self.kind),
+            false => self.outer.and_then(|outer| outer.defined_in(name))
+        }
+    }
+
+    /// Records the fields of the struct whose whole python path is `path`. They are kept by the global scope,
+    /// so structs in other namespaces can inherit them
+    pub(crate) fn define_struct(&self, path: String, fields: HashMap<String, Symbol>) {
+        match self.outer {
+            Some(outer) => outer.define_struct(path, fields),
+            None => {
+                self.struct_fields.borrow_mut().insert(path, fields);
+            }
+        }
+    }
+
+    /// Fields of the struct whose whole python path is `path`
+    pub(crate) fn struct_fields(&self, path: &str) -> Option<HashMap<String, Symbol>> {
+        match self.outer {
+            Some(outer) => outer.struct_fields(path),
+            None => self.struct_fields.borrow().get(path).cloned()
+        }
+    }
+
+    /// Records that the enum whose whole python path is `path` exists. It's kept by the global scope, so its entries
+    /// are known in other namespaces too
+    pub(crate) fn define_enum(&self, path: String) {
+        match self.outer {
+            Some(outer) => outer.define_enum(path),
+            None => {
+                self.enums.borrow_mut().insert(path);
+            }
+        }
+    }
+
+    /// Whether `path` is the whole python path of an enum
+    pub(crate) fn is_enum(&self, path: &str) -> bool {
+        match self.outer {
+            Some(outer) => outer.is_enum(path),
+            None => self.enums.borrow().contains(path)
+        }
+    }
+
+    pub(crate) fn define(&self, name: String, symbol: Symbol) {
+        self.symbols.borrow_mut().insert(name, symbol);
+    }
+
+    /// What `name` refers to, looking from the innermost scope outwards
+    pub(crate) fn lookup(&self, name: &str) -> Option<Symbol> {
+        match self.symbols.borrow().get(name) {
+            Some(symbol) => Some(symbol.clone()),
+            None => self.outer.and_then(|outer| outer.lookup(name))
+        }
+    }
 }
 
-pub(crate) fn vec_translate_exprs(exprs: Vec<Spanned<Expr>>, lvl: usize, context: StatementsContext) -> PyLines {
-    panic!("Didn't take context into account");
+pub(crate) fn vec_translate_statements(stmnts: Vec<Spanned<Statement>>, lvl: usize, context: &Scope) -> PyLines {
     let mut lines = Vec::new();
-    for expr in exprs {
-        lines.extend(translate_expr(expr.0, lvl, context))
+    for stmnt in stmnts {
+        lines.extend(translate_statement(stmnt, lvl, context))
     }
 
     unkown_py_lines(lines)
 }
 
-fn translate_hextype(htype: HexType, lvl: usize, context: StatementsContext) -> PyLine {
+fn translate_hextype(htype: HexType, lvl: usize, context: &Scope) -> PyLine {
     match htype {
-        HexType::Custom(htype) => PyLine {indent_lvl: lvl, line: htype},
-        HexType::Path(a) => PyLine {indent_lvl: lvl, line: "None".to_string()}, // TODO
+        HexType::Custom(htype) => PyLine {indent_lvl: lvl, line: resolve_path(htype.split("::"), context)},
+        // Namespaces are translated to classes, so my::Header becomes my.Header
+        HexType::Path(path) => PyLine {indent_lvl: lvl, line: resolve_path(path.iter().map(String::as_str), context)},
         HexType::V(v) => match v {
-           ValueType::CustomType => PyLine {indent_lvl: lvl, line: "None".to_string()}, // TODO
+           ValueType::CustomType => unreachable!("Custom types are parsed as HexType::Custom or HexType::Path"),
            ValueType::Padding => PyLine {indent_lvl: lvl, line: "padding".to_string()}, // TODO
            ValueType::Auto => PyLine {indent_lvl: lvl, line: "auto".to_string()},
            ValueType::U8 => PyLine {indent_lvl: lvl, line: "u8".to_string()},
@@ -130,7 +473,7 @@ fn translate_hextype(htype: HexType, lvl: usize, context: StatementsContext) ->
            ValueType::Boolean => PyLine {indent_lvl: lvl, line: "Bool".to_string()},
            ValueType::Character => PyLine {indent_lvl: lvl, line: "char".to_string()},
            ValueType::Character16 => PyLine {indent_lvl: lvl, line: "char16".to_string()},
-           ValueType::String => PyLine {indent_lvl: lvl, line: "None".to_string()}, // TODO
+           ValueType::String => PyLine {indent_lvl: lvl, line: "str".to_string()},
         },
         HexType::Parameted(htype, b) => translate_hextype(htype.as_ref().to_owned(), lvl, context), // TODO
         HexType::Null => PyLine {indent_lvl: lvl, line: "None".to_string()},
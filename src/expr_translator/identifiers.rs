@@ -0,0 +1,209 @@
+//! Hexpat names that can't be used as they are in python. They get a trailing underscore, `class` becomes `class_`,
+//! or a number too if the pattern has a name like that, so two names never become the same one.
+//! Python renames names with two leading underscores inside of classes, so those get an `h` in front, `__len` becomes `h__len`.
+//! Also the names of the temporaries of the translated code, which must not be any of the names of the pattern.
+//! Every name of the pattern goes through `mangle`, which refuses anything that isn't a plain name, so patterns can't put code in the translation
+
+use std::{cell::RefCell, collections::{HashMap, HashSet}};
+
+use super::refuse;
+
+/// Reserved words, python would fail to parse them as names
+const KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// Builtins that would be shadowed. Translated code uses some of them, like `isinstance` and `super`
+const BUILTINS: &[&str] = &[
+    "Ellipsis", "Exception", "NotImplemented", "abs", "aiter", "all", "anext", "any", "ascii", "bin",
+    "bool", "breakpoint", "bytearray", "bytes", "callable", "chr", "classmethod", "compile", "complex",
+    "copyright", "credits", "delattr", "dict", "dir", "divmod", "enumerate", "eval", "exec", "exit",
+    "filter", "float", "format", "frozenset", "getattr", "globals", "hasattr", "hash", "help", "hex",
+    "id", "input", "int", "isinstance", "issubclass", "iter", "len", "license", "list", "locals", "map",
+    "max", "memoryview", "min", "next", "object", "oct", "open", "ord", "pow", "print", "property",
+    "quit", "range", "repr", "reversed", "round", "set", "setattr", "slice", "sorted", "staticmethod",
+    "str", "sum", "super", "tuple", "type", "vars", "zip",
+];
+
+/// Names translated code gives a meaning to: `self` and what the header imports from rs_hexpyt
+const RESERVED: &[&str] = &[
+    "self", "Dollar", "Struct", "BitField", "IntStruct",
+    "Float", "double", "char", "char16", "Bool", "Padding", "Array", "String", "Enum", "namespace",
+    "sizeof", "addressof", "hexpat_div", "hexpat_wrap", "placed", "placed_patterns",
+];
+
+/// Functions of rs_hexpyt that patterns call by their hexpat name
+const RUNTIME_FUNCTIONS: &[&str] = &["sizeof", "addressof"];
+
+/// Methods and attributes of rs_hexpyt's Struct and BitField. A field with one of these names would replace them
+const FIELD_METHODS: &[&str] = &[
+    "address", "attributes", "break_", "breaked", "continue_", "dollar", "dump", "entries", "fields", "formatted", "hidden",
+    "init_struct", "init_union", "name", "parent", "set_attributes", "set_big_endian", "set_name",
+    "set_parent", "size", "transformed", "init_bitfield", "msb_first", "read_bits", "bits",
+    BITFIELD_MSB_FIRST, BITFIELD_SIZE,
+    // What rs_hexpyt keeps in the instances
+    "____name________", "___big__endian_____", "___bits_____", "___bits__read_____", "___breaked___",
+    "___continued___", "___dollar______", "___false_range_____", "___length__", "___length_____",
+    "___length______", "___length_______", "___length________", "___parent_____", "___start_____",
+    "___transformed_____", "___true_range_____", "___type_____", "___value___", "___value_____",
+    "___value_______", "__address____", "__hexpat_attributes__", "__size_______",
+];
+
+/// Methods and class attributes enums have on top of the ones of Struct
+const ENUM_METHODS: &[&str] = &[
+    "value", "length", "to_dollar", "entry", "member", "Members",
+    ENUM_DICT, ENUM_PYTHON_NAMES, ENUM_RANGES, ENUM_FLAGS,
+];
+
+// Class attributes rs_hexpyt reads from translated types. Fields and entries with these names are mangled
+
+/// Whether a bitfield is read from the most significant bit
+pub(crate) const BITFIELD_MSB_FIRST: &str = "_bit_field___msb_first";
+/// Bits of the container a bitfield is read from
+pub(crate) const BITFIELD_SIZE: &str = "_bit_field___size";
+/// Hexpat name of each value of an enum
+pub(crate) const ENUM_DICT: &str = "_enum__dict___";
+/// Python name of each entry of an enum whose name was mangled
+pub(crate) const ENUM_PYTHON_NAMES: &str = "_enum__python__names___";
+/// Ranges of values of an enum, with their hexpat names
+pub(crate) const ENUM_RANGES: &str = "_enum__ranges___";
+/// Whether an enum is a set of flags
+pub(crate) const ENUM_FLAGS: &str = "_enum__flags___";
+
+/// Whether python reads `name` as a single name. Hexpat names are ASCII, python would accept other
+/// characters too but normalizes them, which can make two different names the same one
+fn is_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// `name`, if it's a name and not code. Otherwise the pattern is refused, and a placeholder takes its place
+pub(crate) fn checked(name: &str) -> &str {
+    if !is_name(name) {
+        refuse(format!("{name:?} isn't a valid name"));
+        return "refused"
+    }
+
+    name
+}
+
+/// `name`, or if it `is_reserved` the first of `name_`, `name_1`, `name_2`... that isn't reserved or written in the pattern.
+/// Reserved names don't end in `_` and a number, so no two names get the same one.
+/// Names with two leading underscores try `hname` first
+fn renamed(name: &str, is_reserved: impl Fn(&str) -> bool) -> String {
+    let name = checked(name);
+    let is_private = name.starts_with("__");
+    if !is_private && !is_reserved(name) {
+        return name.to_string()
+    }
+    let name = match is_private {
+        true => format!("h{name}"),
+        false => name.to_string()
+    };
+    WRITTEN.with(|written| {
+        let written = written.borrow();
+        std::iter::once(name.clone())
+            .filter(|_| is_private)
+            .chain(std::iter::once(format!("{name}_")))
+            .chain((1..).map(|i| format!("{name}_{i}")))
+            .find(|candidate| !is_reserved(candidate) && !written.contains(candidate))
+            .expect("There are infinitely many candidates")
+    })
+}
+
+fn is_reserved(name: &str) -> bool {
+    KEYWORDS.contains(&name) || BUILTINS.contains(&name) || RESERVED.contains(&name)
+}
+
+fn is_reserved_field(name: &str) -> bool {
+    FIELD_METHODS.contains(&name) || is_reserved(name)
+}
+
+fn is_reserved_entry(name: &str) -> bool {
+    ENUM_METHODS.contains(&name) || is_reserved_field(name)
+}
+
+/// Whether a call to `name` is a call to the function of rs_hexpyt with that name
+pub(crate) fn is_runtime_function(name: &str) -> bool {
+    RUNTIME_FUNCTIONS.contains(&name)
+}
+
+/// Python name of a hexpat variable, function or type
+pub(crate) fn mangle(name: &str) -> String {
+    renamed(name, is_reserved)
+}
+
+/// Python name of a hexpat field, which is an attribute of `self`
+pub(crate) fn mangle_field(name: &str) -> String {
+    renamed(name, is_reserved_field)
+}
+
+/// Python name of an enum entry, which is an attribute of the enum class
+pub(crate) fn mangle_entry(name: &str) -> String {
+    renamed(name, is_reserved_entry)
+}
+
+/// Python path of a hexpat path, `std::mem::size` becomes `std.mem.size`
+pub(crate) fn mangle_path(path: &str) -> String {
+    path.split("::").map(mangle).collect::<Vec<_>>().join(".")
+}
+
+/// Whether the python name of a field isn't its hexpat name, so the hexpat one has to be kept for dumps
+pub(crate) fn is_mangled(name: &str) -> bool {
+    mangle_field(name) != name
+}
+
+// Preferred names of the temporaries of the translated code. The names they get are the ones `generated` gives
+
+/// `$`, the offset being read
+pub(crate) const DOLLAR: &str = "_dollar___offset";
+/// Where the struct being read started
+pub(crate) const DOLLAR_COPY: &str = "_dollar___offset_copy";
+/// The data, read by the template in the header
+pub(crate) const BYTES: &str = "byts";
+
+thread_local! {
+    /// Every identifier written in the file being translated, in any scope
+    static WRITTEN: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+    /// Every identifier written in the file being translated, as written and mangled, and the names of the temporaries
+    static USED: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+    /// Name each temporary got
+    static GENERATED: RefCell<HashMap<&'static str, String>> = RefCell::new(HashMap::new());
+}
+
+/// Records the identifiers of the file being translated, so temporaries don't get any of their names
+pub(crate) fn set_used_names(source: &str) {
+    let written = source.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|word| is_name(word))
+        .map(str::to_string)
+        .collect::<HashSet<_>>();
+    WRITTEN.with(|w| *w.borrow_mut() = written.clone());
+    let used = written.iter()
+        .flat_map(|word| [mangle(word), mangle_field(word), mangle_entry(word)])
+        .chain(written.iter().cloned())
+        .collect();
+    USED.with(|u| *u.borrow_mut() = used);
+    GENERATED.with(|g| g.borrow_mut().clear());
+}
+
+/// Name of a temporary of the translated code. It's `preferred`, or `preferred` with a number if the pattern uses that name.
+/// The same temporary always gets the same name
+pub(crate) fn generated(preferred: &'static str) -> String {
+    GENERATED.with(|generated| generated.borrow_mut()
+        .entry(preferred)
+        .or_insert_with(|| USED.with(|used| {
+            let mut used = used.borrow_mut();
+            let name = std::iter::once(preferred.to_string())
+                .chain((1..).map(|i| format!("{preferred}{i}")))
+                .find(|name| !used.contains(name))
+                .expect("There are infinitely many candidates");
+            used.insert(name.clone());
+            name
+        }))
+        .clone()
+    )
+}
@@ -0,0 +1,195 @@
+//! Python literals of hexpat literals. The parser keeps numbers as floats and strings with their escapes resolved,
+//! which loses big integers and whether `\xE9` or `é` was written, so the token of the literal is used when it
+//! agrees with what the parser read
+
+use super::tokens::{tokens_between, Token, TokenKind};
+
+/// A number literal, as python writes it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Number {
+    Integer(String),
+    Float(String),
+}
+
+impl Number {
+    pub(crate) fn is_float(&self) -> bool {
+        matches!(self, Number::Float(_))
+    }
+
+    pub(crate) fn python(self) -> String {
+        match self {
+            Number::Integer(literal) | Number::Float(literal) => literal,
+        }
+    }
+}
+
+/// The token of the literal the parser read from `start..end`, if it's in the file being translated
+fn literal_token((start, end): (usize, usize), kind: TokenKind) -> Option<Token> {
+    match tokens_between(start, end).as_slice() {
+        [token] if token.kind == kind => Some(token.clone()),
+        _ => None
+    }
+}
+
+/// The number the parser read as `n` from `start..end`
+pub(crate) fn number(n: f64, span: (usize, usize)) -> Number {
+    literal_token(span, TokenKind::Number)
+        .and_then(|token| spelled_number(&token.text))
+        .filter(|(_, value)| (value - n).abs() <= n.abs() * f64::EPSILON)
+        .map_or_else(|| parsed_number(n), |(number, _)| number)
+}
+
+/// A number without its spelling. Integers are written whole, even the ones too big for a float to hold exactly
+fn parsed_number(n: f64) -> Number {
+    match n.is_finite() && n.fract() == 0.0 {
+        true if n < 0.0 => Number::Integer(format!("{}", n as i128)),
+        true => Number::Integer(format!("{}", n as u128)),
+        false => Number::Float(format!("{n:?}")),
+    }
+}
+
+/// Python literal of a number written in hexpat and its value. None if `spelling` isn't a number
+pub(crate) fn spelled_number(spelling: &str) -> Option<(Number, f64)> {
+    let spelling = spelling.replace('_', "");
+    let is_integer = |digits: &str, radix: u32| !digits.is_empty() && digits.chars().all(|c| c.is_digit(radix));
+
+    // 0xFF, 0b1010, 0o17, optionally with an unsigned suffix (10u)
+    let integer = spelling.trim_end_matches(|c| c == 'u' || c == 'U');
+    for (prefix, radix) in [("0x", 16), ("0b", 2), ("0o", 8)] {
+        if integer.get(..2).map_or(false, |start| start.eq_ignore_ascii_case(prefix)) {
+            let digits = &integer[2..];
+            let value = u128::from_str_radix(digits, radix).ok().filter(|_| is_integer(digits, radix))?;
+            return Some((Number::Integer(format!("{prefix}{digits}")), value as f64))
+        }
+    }
+    if is_integer(integer, 10) {
+        // Python doesn't allow leading zeroes
+        let digits = integer.trim_start_matches('0');
+        let digits = if digits.is_empty() { "0" } else { digits };
+        let value = digits.parse::<u128>().ok()?;
+        return Some((Number::Integer(digits.to_string()), value as f64))
+    }
+
+    // 1.5, 1e10, 2.0F, .5D, 1f
+    let is_suffixed = spelling.ends_with(|c| matches!(c, 'f' | 'F' | 'd' | 'D'));
+    let float = spelling.trim_end_matches(|c| matches!(c, 'f' | 'F' | 'd' | 'D'));
+    if float.contains(|c| matches!(c, '.' | 'e' | 'E')) || (is_suffixed && is_integer(float, 10)) {
+        let value = float.parse::<f64>().ok()?;
+        let float = if float.starts_with('.') { format!("0{float}") } else { float.to_string() };
+        let float = if float.ends_with('.') { format!("{float}0") } else { float };
+        // 1f
+        let float = if float.contains(|c| matches!(c, '.' | 'e' | 'E')) { float } else { format!("{float}.0") };
+        return Some((Number::Float(float), value))
+    }
+
+    None
+}
+
+/// Whether `python` is an integer literal (`7`, `-7`, `0xFF`) that an integer of `bits` holds
+pub(crate) fn fits(python: &str, bits: usize, signed: bool) -> bool {
+    let (negative, magnitude) = match python.strip_prefix('-') {
+        Some(magnitude) => (true, magnitude),
+        None => (false, python)
+    };
+    let radix = match magnitude.get(..2) {
+        Some("0x") => 16,
+        Some("0b") => 2,
+        Some("0o") => 8,
+        _ => 10
+    };
+    let digits = if radix == 10 { magnitude } else { &magnitude[2..] };
+    let magnitude = match u128::from_str_radix(digits, radix) {
+        // from_str_radix takes a leading +
+        Ok(magnitude) if !digits.starts_with('+') => magnitude,
+        _ => return false
+    };
+
+    match (negative, signed) {
+        (false, false) => bits >= 128 || magnitude < 1 << bits,
+        (false, true) => magnitude < 1 << (bits - 1),
+        (true, true) => magnitude <= 1 << (bits - 1),
+        (true, false) => magnitude == 0,
+    }
+}
+
+/// Python literal of the string the parser read as `s` from `start..end`. Hexpat strings are bytes, and the runtime
+/// reads each byte as the character with that code. Escapes (`\xE9`) are the byte they name, and the characters
+/// written in the source (`é`) are their UTF-8 bytes
+pub(crate) fn string(s: &str, span: (usize, usize)) -> String {
+    let bytes = literal_token(span, TokenKind::Str)
+        .and_then(|token| unquoted(&token.text).and_then(spelled_characters))
+        .filter(|characters| characters.iter().map(|(c, _)| *c).eq(s.chars()))
+        .map(|characters| characters.into_iter().flat_map(|(_, bytes)| bytes).collect::<Vec<_>>())
+        // Without its spelling, each character is taken as written in the source
+        .unwrap_or_else(|| s.as_bytes().to_vec());
+
+    let escaped = bytes.into_iter().map(escape_byte).collect::<String>();
+    format!("\"{escaped}\"")
+}
+
+/// Python literal of a hexpat character. A character is a single code, whether it's escaped (`'\xFF'`) or written
+/// in the source (`'é'`, for char16)
+pub(crate) fn character(c: char) -> String {
+    let escaped = match u8::try_from(c) {
+        Ok(byte) => escape_byte(byte),
+        Err(_) => format!("\\U{:08x}", c as u32)
+    };
+
+    format!("\"{escaped}\"")
+}
+
+/// Text between the quotes of a string token
+fn unquoted(token: &str) -> Option<&str> {
+    token.strip_prefix('"')?.strip_suffix('"')
+}
+
+/// Characters of the inside of a string literal, each with the bytes it stands for. None if it has an unknown escape
+fn spelled_characters(spelling: &str) -> Option<Vec<(char, Vec<u8>)>> {
+    let mut characters = Vec::new();
+    let mut chars = spelling.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            characters.push((c, c.to_string().into_bytes()));
+            continue
+        }
+        let escaped = match chars.next()? {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            '0' => '\0',
+            'a' => '\x07',
+            'b' => '\x08',
+            'f' => '\x0c',
+            'v' => '\x0b',
+            c @ ('\\' | '"' | '\'') => c,
+            'x' => {
+                let digits = chars.by_ref().take(2).collect::<String>();
+                let byte = u8::from_str_radix(&digits, 16).ok().filter(|_| digits.len() == 2)?;
+                characters.push((char::from(byte), vec![byte]));
+                continue
+            },
+            'u' => {
+                let digits = chars.by_ref().take(4).collect::<String>();
+                let c = u32::from_str_radix(&digits, 16).ok().filter(|_| digits.len() == 4).and_then(char::from_u32)?;
+                characters.push((c, c.to_string().into_bytes()));
+                continue
+            },
+            _ => return None
+        };
+        characters.push((escaped, vec![escaped as u8]));
+    }
+
+    Some(characters)
+}
+
+fn escape_byte(byte: u8) -> String {
+    match byte {
+        b'"' => "\\\"".to_string(),
+        b'\\' => "\\\\".to_string(),
+        b'\n' => "\\n".to_string(),
+        b'\r' => "\\r".to_string(),
+        b'\t' => "\\t".to_string(),
+        0x20..=0x7E => (byte as char).to_string(),
+        _ => format!("\\x{byte:02x}")
+    }
+}
@@ -0,0 +1,153 @@
+//! Tokens of the file being translated. The parser drops some of what is written (the types of bitfield entries,
+//! the parents of structs, attributes after a body), so it's read from these. Comments and whitespace aren't tokens,
+//! so they can't change what is read
+
+use std::cell::RefCell;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TokenKind {
+    /// `a`, `u8`. `a::b` is a name, two `:` and a name
+    Name,
+    /// `0x10`, `1.5`, `1e10`
+    Number,
+    /// `"abc"`, quotes included
+    Str,
+    /// `'a'`, quotes included
+    Char,
+    /// Any other character
+    Punctuation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Token {
+    pub(crate) kind: TokenKind,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) text: String,
+}
+
+impl Token {
+    pub(crate) fn is(&self, text: &str) -> bool {
+        self.text == text
+    }
+}
+
+thread_local! {
+    /// Tokens of the file being translated, in order
+    static TOKENS: RefCell<Vec<Token>> = RefCell::new(Vec::new());
+}
+
+pub(crate) fn set_tokens(source: &str) {
+    TOKENS.with(|t| *t.borrow_mut() = tokenize(source))
+}
+
+/// Tokens that are entirely inside of `start..end`. Tokens don't overlap and are sorted, so they are searched for
+pub(crate) fn tokens_between(start: usize, end: usize) -> Vec<Token> {
+    TOKENS.with(|t| {
+        let tokens = t.borrow();
+        let first = tokens.partition_point(|token| token.start < start);
+        let last = first + tokens[first..].partition_point(|token| token.end <= end);
+        tokens[first..last].to_vec()
+    })
+}
+
+/// Tokens from `start` to the end of the file
+pub(crate) fn tokens_from(start: usize) -> Vec<Token> {
+    tokens_between(start, usize::MAX)
+}
+
+/// The token at exactly `start..end`. Spans of statements from included files point into other files,
+/// so what's read for them is only trusted if it is spelled here like the parser says
+pub(crate) fn token_at(start: usize, end: usize) -> Option<Token> {
+    TOKENS.with(|t| {
+        let tokens = t.borrow();
+        tokens.get(tokens.partition_point(|token| token.start < start))
+            .filter(|token| token.start == start && token.end == end)
+            .cloned()
+    })
+}
+
+/// Whether `text` is written at `start..end`
+pub(crate) fn is_spelled(start: usize, end: usize, text: &str) -> bool {
+    token_at(start, end).map_or(false, |token| token.is(text))
+}
+
+/// Whether `start..end` starts and ends on tokens of the file being translated. Spans of statements from included
+/// files point into other files, so they mostly don't
+pub(crate) fn is_token_span(start: usize, end: usize) -> bool {
+    TOKENS.with(|t| {
+        let tokens = t.borrow();
+        let starts = tokens.get(tokens.partition_point(|token| token.start < start)).map_or(false, |token| token.start == start);
+        let ends = tokens.get(tokens.partition_point(|token| token.end < end)).map_or(false, |token| token.end == end);
+        starts && ends
+    })
+}
+
+fn tokenize(source: &str) -> Vec<Token> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < source.len() {
+        let start = i;
+        let c = source[i..].chars().next().unwrap_or_default();
+        let kind = match c {
+            c if c.is_whitespace() => {
+                i += c.len_utf8();
+                continue
+            },
+            '/' if bytes.get(i+1) == Some(&b'/') => {
+                i = source[i..].find('\n').map_or(source.len(), |end| i + end);
+                continue
+            },
+            '/' if bytes.get(i+1) == Some(&b'*') => {
+                i = source[i+2..].find("*/").map_or(source.len(), |end| i + 2 + end + 2);
+                continue
+            },
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                i += source[i..].find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(source.len() - i);
+                TokenKind::Name
+            },
+            c if c.is_ascii_digit() => {
+                let is_hex = source[i..].starts_with("0x") || source[i..].starts_with("0X");
+                while i < source.len() {
+                    match bytes[i] {
+                        b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z' | b'_' | b'.' => i += 1,
+                        // 1e-5
+                        b'+' | b'-' if !is_hex && matches!(bytes[i-1], b'e' | b'E') => i += 1,
+                        _ => break
+                    }
+                }
+                TokenKind::Number
+            },
+            quote @ ('"' | '\'') => {
+                i += 1;
+                while i < source.len() {
+                    match bytes[i] {
+                        b'\\' => i += 2,
+                        b if b == quote as u8 => {
+                            i += 1;
+                            break
+                        },
+                        _ => i += 1
+                    }
+                }
+                i = i.min(source.len());
+                // Escapes can skip into the middle of a character
+                while !source.is_char_boundary(i) {
+                    i += 1;
+                }
+                match quote {
+                    '"' => TokenKind::Str,
+                    _ => TokenKind::Char
+                }
+            },
+            c => {
+                i += c.len_utf8();
+                TokenKind::Punctuation
+            }
+        };
+        tokens.push(Token { kind, start, end: i, text: source[start..i].to_string() });
+    }
+
+    tokens
+}
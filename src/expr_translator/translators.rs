@@ -1,10 +1,16 @@
-use hexparser::{token::Spanned, Value, Expr, m_parser::{UnaryOp, HexTypeDef, BinaryOp, FuncArgument, MatchBranch, Statement, AssignmentOp}};
+use std::collections::HashMap;
 
-use crate::{PyLines, one_py_line, unkown_py_lines, PyLine, expr_translator::{vec_translate_exprs, translate_statement}};
+use hexparser::{token::Spanned, Value, Expr, m_parser::{UnaryOp, HexTypeDef, BinaryOp, FuncArgument, MatchBranch, Statement, AssignmentOp, Definition, Endianness, HexType}};
 
-use super::{translate_expr, vec_translate_statements, translate_hextype, StatementsContext};
+use crate::{PyLines, one_py_line, unkown_py_lines, PyLine, expr_translator::translate_statement, type_inference::{type_of, InferredType}};
 
-pub(crate) fn translate_value(val: Value, lvl: usize, context: StatementsContext) -> PyLines {
+use super::tokens::{tokens_between, tokens_from, is_spelled, Token, TokenKind};
+use super::literals::{self, spelled_number, Number};
+use super::identifiers::{checked, is_runtime_function, mangle, mangle_field, mangle_entry, mangle_path, is_mangled, generated, DOLLAR, DOLLAR_COPY, BYTES, BITFIELD_MSB_FIRST, BITFIELD_SIZE, ENUM_DICT, ENUM_PYTHON_NAMES, ENUM_RANGES, ENUM_FLAGS};
+use super::{translate_expr, vec_translate_statements, translate_hextype, unsupported, report, refuse, is_initialization, struct_parents, resolve_path, qualified_path, StatementsContext, Scope, Symbol, SymbolKind};
+
+/// `span` is where the value is written, for the spelling of literals
+pub(crate) fn translate_value(val: Value, span: (usize, usize), lvl: usize, context: &Scope) -> PyLines {
     match val {
         Value::Null => one_py_line(lvl, "None".to_string()),
         Value::Bool(b) => if b {
@@ -12,39 +18,72 @@ pub(crate) fn translate_value(val: Value, lvl: usize, context: StatementsContext
         } else {
             one_py_line(lvl, "False".to_string())
         },
-        Value::Num(n) => one_py_line(lvl, n.to_string()),
-        Value::Str(s) => one_py_line(lvl, s),
-        Value::Char(c) => one_py_line(lvl, c.to_string()),
-        Value::Func(f) => one_py_line(lvl, f),
+        Value::Num(n) => one_py_line(lvl, literals::number(n, span).python()),
+        Value::Str(s) => one_py_line(lvl, literals::string(&s, span)),
+        Value::Char(c) => one_py_line(lvl, literals::character(c)),
+        Value::Func(f) => one_py_line(lvl, mangle_path(&f)),
     }
 }
 
-pub(crate) fn translate_expr_list(list: Vec<Spanned<Expr>>, lvl: usize, context: StatementsContext) -> PyLines {
+pub(crate) fn translate_expr_list(list: Vec<Spanned<Expr>>, lvl: usize, context: &Scope) -> PyLines {
     unkown_py_lines(list.into_iter()
-            .map(|(expr, _)| translate_expr(expr, lvl, context))
+            .map(|expr| translate_expr(expr, lvl, context))
             .flatten()
             .collect::<Vec<_>>())
 }
 
-pub(crate) fn translate_unary(operation: UnaryOp, operand: Box<Spanned<Expr>>, lvl: usize, context: StatementsContext) -> PyLines {
-    panic!("Didn't take context into account");
-    let operand = translate_expr(operand.0, lvl, context);
+pub(crate) fn translate_unary(operation: UnaryOp, operand: Box<Spanned<Expr>>, lvl: usize, context: &Scope) -> PyLines {
+    let precedence = match operation {
+        UnaryOp::LNot => precedence::NOT,
+        _ => precedence::UNARY
+    };
+    let parenthesized = needs_parentheses(&operand, precedence, false);
+    // ~ on a python int is negative, unsigned values keep their width
+    let width = match type_of(operand.as_ref()).width() {
+        Some((bits, false)) => Some(bits),
+        _ => None
+    };
+    let operand = translate_expr(*operand, lvl, context);
     let operand = operand.unwrap_one().line;
-
-    match operation {
-        UnaryOp::Add => one_py_line(lvl, format!("+{}", operand)),
-        UnaryOp::Sub => one_py_line(lvl, format!("-{}", operand)),
-        UnaryOp::LNot => one_py_line(lvl, format!("not {}", operand)),
-        UnaryOp::BNot => one_py_line(lvl, format!("!{}", operand)),
+    let operand = if parenthesized { format!("({operand})") } else { operand };
+
+    match (operation, width) {
+        (UnaryOp::Add, _) => one_py_line(lvl, format!("+{}", operand)),
+        (UnaryOp::Sub, _) => one_py_line(lvl, format!("-{}", operand)),
+        (UnaryOp::LNot, _) => one_py_line(lvl, format!("not {}", operand)),
+        (UnaryOp::BNot, Some(bits)) => one_py_line(lvl, format!("hexpat_wrap(~{}, {bits}, False)", operand)),
+        (UnaryOp::BNot, None) => one_py_line(lvl, format!("~{}", operand)),
     }
 }
 
-pub(crate) fn translate_binary(loperand: Box<Spanned<Expr>>, operator: BinaryOp, roperand: Box<Spanned<Expr>>, lvl: usize, context: StatementsContext) -> PyLines {
+pub(crate) fn translate_binary(loperand: Box<Spanned<Expr>>, operator: BinaryOp, roperand: Box<Spanned<Expr>>, lvl: usize, context: &Scope) -> PyLines {
+    // The AST already has the order of evaluation of hexpat. Python's precedence is different,
+    // so operands that would be grouped differently get parentheses
+    let precedence = binary_precedence(&operator);
+    let lparenthesized = needs_parentheses(&loperand, precedence, false);
+    let rparenthesized = needs_parentheses(&roperand, precedence, true);
+    let division = division_operator(&loperand, &roperand);
+    // Operators that python doesn't have are calls, so their operands never need parentheses
+    match operator {
+        // Truncating division for integers, true division for floats. If the types aren't known, it's decided when running
+        BinaryOp::Div if division.is_none() => {
+            let loperand = translate_expr(*loperand, lvl, context).unwrap_one().line;
+            let roperand = translate_expr(*roperand, lvl, context).unwrap_one().line;
+            return one_py_line(lvl, format!("hexpat_div({loperand}, {roperand})"))
+        },
+        // ^ would compare bits, 2 ^^ 1 is false
+        BinaryOp::LXor => {
+            let loperand = translate_expr(*loperand, lvl, context).unwrap_one().line;
+            let roperand = translate_expr(*roperand, lvl, context).unwrap_one().line;
+            return one_py_line(lvl, format!("bool({loperand}) != bool({roperand})"))
+        },
+        _ => ()
+    }
     let operator = match operator {
         BinaryOp::Add => "+",
         BinaryOp::Sub => "-",
         BinaryOp::Mul => "*",
-        BinaryOp::Div => "/",
+        BinaryOp::Div => division.expect("hexpat_div was already handled"),
         BinaryOp::Eq => "==",
         BinaryOp::NotEq => "!=",
         BinaryOp::Mod => "%",
@@ -58,7 +97,7 @@ pub(crate) fn translate_binary(loperand: Box<Spanned<Expr>>, operator: BinaryOp,
         BinaryOp::Greater => ">",
         BinaryOp::Less => "<",
         BinaryOp::LAnd => "and",
-        BinaryOp::LXor => "^",
+        BinaryOp::LXor => unreachable!(),
         BinaryOp::LOr => "or",
         BinaryOp::Assign(a) => match a {
             AssignmentOp::Just => "=",
@@ -74,252 +113,780 @@ pub(crate) fn translate_binary(loperand: Box<Spanned<Expr>>, operator: BinaryOp,
             AssignmentOp::BXor => "^=",
         },
     };
-    let loperand = translate_expr(loperand.0, lvl, context);
-    let roperand = translate_expr(roperand.0, lvl, context);
+    let loperand = translate_expr(*loperand, lvl, context);
+    let roperand = translate_expr(*roperand, lvl, context);
 
     let loperand = loperand.unwrap_one().line;
     let roperand = roperand.unwrap_one().line;
+    let loperand = if lparenthesized { format!("({loperand})") } else { loperand };
+    let roperand = if rparenthesized { format!("({roperand})") } else { roperand };
 
     let line = format!("{loperand} {operator} {roperand}");
     one_py_line(lvl, line)
 }
 
-pub(crate) fn translate_assignment(loperand: Box<Spanned<Expr>>, operator: AssignmentOp, roperand: Box<Spanned<Expr>>, lvl: usize, context: StatementsContext) -> PyLines {
-    let (operator, say_type) = match operator {
-        AssignmentOp::Just => ("=", true),
-        AssignmentOp::Add => ("+=", false),
-        AssignmentOp::Sub => ("-=", false),
-        AssignmentOp::Mul => ("*=", false),
-        AssignmentOp::Div => ("/=", false),
-        AssignmentOp::Mod => ("%=", false),
-        AssignmentOp::RShift => (">>=", false),
-        AssignmentOp::LShift => ("<<=", false),
-        AssignmentOp::BOr => ("|=", false),
-        AssignmentOp::BAnd => ("&=", false),
-        AssignmentOp::BXor => ("^=", false),
-    };
-    let loperand = translate_expr(loperand.0, lvl, context);
-    let roperand = translate_expr(roperand.0, lvl, context);
+/// Python precedences, from lowest to highest
+mod precedence {
+    pub(super) const LAMBDA: u8 = 0;
+    pub(super) const TERNARY: u8 = 1;
+    pub(super) const OR: u8 = 2;
+    pub(super) const AND: u8 = 3;
+    pub(super) const NOT: u8 = 4;
+    pub(super) const COMPARISON: u8 = 5;
+    pub(super) const BOR: u8 = 6;
+    pub(super) const BXOR: u8 = 7;
+    pub(super) const BAND: u8 = 8;
+    pub(super) const SHIFT: u8 = 9;
+    pub(super) const SUM: u8 = 10;
+    pub(super) const PRODUCT: u8 = 11;
+    pub(super) const UNARY: u8 = 12;
+    pub(super) const ATOM: u8 = 13;
+}
 
-    let loperand = loperand.unwrap_one().line;
+/// Precedence of the python operator a hexpat operator is translated to
+fn binary_precedence(operator: &BinaryOp) -> u8 {
+    match operator {
+        BinaryOp::LOr => precedence::OR,
+        BinaryOp::LAnd => precedence::AND,
+        BinaryOp::Eq
+        | BinaryOp::NotEq
+        | BinaryOp::LXor
+        | BinaryOp::GreaterEqual
+        | BinaryOp::LessEqual
+        | BinaryOp::Greater
+        | BinaryOp::Less => precedence::COMPARISON,
+        BinaryOp::BOr => precedence::BOR,
+        BinaryOp::BXor => precedence::BXOR,
+        BinaryOp::BAnd => precedence::BAND,
+        BinaryOp::LShift | BinaryOp::RShift => precedence::SHIFT,
+        BinaryOp::Add | BinaryOp::Sub => precedence::SUM,
+        BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod => precedence::PRODUCT,
+        BinaryOp::Assign(_) => precedence::LAMBDA,
+    }
+}
 
-    let roperand = roperand.unwrap_one().line;
+/// Precedence of the python expression `expr` is translated to
+fn expr_precedence(expr: &Spanned<Expr>) -> u8 {
+    match &expr.0 {
+        // hexpat_div(a, b)
+        Expr::Binary { loperand, operator: BinaryOp::Div, roperand } if division_operator(loperand, roperand).is_none() => precedence::ATOM,
+        Expr::Binary { operator, .. } => binary_precedence(operator),
+        Expr::Unary { operation: UnaryOp::LNot, .. } => precedence::NOT,
+        Expr::Unary { .. } => precedence::UNARY,
+        Expr::Ternary { .. } => precedence::TERNARY,
+        // Array sizes, lambda _dollar___offset: ...
+        Expr::WhileLoop { .. } => precedence::LAMBDA,
+        _ => precedence::ATOM
+    }
+}
 
-    let line = match (context, say_type) {
-        (StatementsContext::None, true) => format!("{loperand} {operator} {roperand}"),
-        (StatementsContext::None, false) => format!("{loperand} {operator} {roperand}"),
-        (StatementsContext::Struct, _) => panic!("Didn't take context into account"),
-        (StatementsContext::Function, _) => format!("{loperand} {operator} {roperand}"),
-        (StatementsContext::Namespace, _) => panic!("Didn't take context into account"),
-        (StatementsContext::Bitfield, _) => panic!("Didn't take context into account"),
-        (StatementsContext::WhileLoop, _) => panic!("Didn't take context into account"),
-        (StatementsContext::ForLoop, _) => panic!("Didn't take context into account"),
-        (StatementsContext::Union, _) => panic!("Didn't take context into account"),
-        (StatementsContext::Try, _) => panic!("Didn't take context into account"),
-    };
+/// Python operator for a hexpat division whose operands have known types. None if it needs `hexpat_div`
+fn division_operator(loperand: &Spanned<Expr>, roperand: &Spanned<Expr>) -> Option<&'static str> {
+    let (ltype, rtype) = (type_of(loperand), type_of(roperand));
+    if ltype.is_float() || rtype.is_float() {
+        Some("/")
+    } else if ltype.is_unsigned() && rtype.is_unsigned() {
+        // Flooring and truncating are the same without negative numbers
+        Some("//")
+    } else {
+        None
+    }
+}
 
-    one_py_line(lvl, line)
+/// Whether an operand of an operator with `precedence` must be in parentheses to keep the order of evaluation.
+/// All binary operators are left associative, and python chains comparisons (`a < b < c`), which hexpat doesn't
+fn needs_parentheses(operand: &Spanned<Expr>, precedence: u8, is_right: bool) -> bool {
+    let operand_precedence = expr_precedence(operand);
+    operand_precedence < precedence
+        || (operand_precedence == precedence && (is_right || precedence == precedence::COMPARISON))
 }
 
-pub(crate) fn translate_ternary(loperand: Box<Spanned<Expr>>, moperand: Box<Spanned<Expr>>, roperand: Box<Spanned<Expr>>, lvl: usize, context: StatementsContext) -> PyLines {
-    panic!("Didn't take context into account");
-    let loperand = translate_expr(loperand.0, lvl, context);
-    let moperand = translate_expr(moperand.0, lvl+1, context);
-    let roperand = translate_expr(roperand.0, lvl+1, context);
+pub(crate) fn translate_assignment(loperand: Box<Spanned<Expr>>, operator: AssignmentOp, roperand: Box<Spanned<Expr>>, lvl: usize, context: &Scope) -> PyLines {
+    let operator = match operator {
+        AssignmentOp::Just => "=",
+        AssignmentOp::Add => "+",
+        AssignmentOp::Sub => "-",
+        AssignmentOp::Mul => "*",
+        AssignmentOp::Div => "/",
+        AssignmentOp::Mod => "%",
+        AssignmentOp::RShift => ">>",
+        AssignmentOp::LShift => "<<",
+        AssignmentOp::BOr => "|",
+        AssignmentOp::BAnd => "&",
+        AssignmentOp::BXor => "^",
+    };
+    // Python ints don't overflow, so variables with a fixed width have to be wrapped by hand
+    if let Expr::Local { name } = &loperand.0 {
+        context.assign(&name.0);
+    }
+    let target_type = match &loperand.0 {
+        Expr::Local { name } => match context.lookup(&name.0) {
+            Some(Symbol { kind: SymbolKind::Local, .. }) => type_of(loperand.as_ref()),
+            _ => InferredType::Unknown
+        },
+        _ => InferredType::Unknown
+    };
+    let width = target_type.width();
+    // Chars are python strings, arithmetic is on their codes
+    let char_bits = match target_type {
+        InferredType::Char => Some(8),
+        InferredType::Char16 => Some(16),
+        _ => None
+    };
+    let division = division_operator(&loperand, &roperand);
+    // a *= 1 + 2 becomes a = a * (1 + 2)
+    let rparenthesized = (width.is_some() || char_bits.is_some()) && match operator {
+        "=" => false,
+        "+" | "-" => needs_parentheses(&roperand, precedence::SUM, true),
+        "*" | "/" | "%" => needs_parentheses(&roperand, precedence::PRODUCT, true),
+        ">>" | "<<" => needs_parentheses(&roperand, precedence::SHIFT, true),
+        "|" => needs_parentheses(&roperand, precedence::BOR, true),
+        "&" => needs_parentheses(&roperand, precedence::BAND, true),
+        _ => needs_parentheses(&roperand, precedence::BXOR, true),
+    };
+    let operator = match (operator, division) {
+        ("/", Some(division)) => division,
+        ("/", None) => "hexpat_div",
+        (operator, _) => operator
+    };
+    let roperand_type = type_of(roperand.as_ref());
+    let loperand = translate_expr(*loperand, lvl, context);
+    let roperand = translate_expr(*roperand, lvl, context);
 
-    let mut lines = vec![
-        PyLine { indent_lvl: lvl, line: "if ".into() }
-    ];
+    let loperand = loperand.unwrap_one().line;
 
-    let first_line = lines[0].line.clone();
-    match loperand {
-        PyLines::One(line) => {
-            lines[0].line = format!("{first_line}, {}:", line.line);
-        },
-        PyLines::Multiple(py_lines) => {
-            lines[0].line = format!("{first_line}(");
-            lines.extend(py_lines.into_iter().map(|line| {
-                PyLine { indent_lvl: lvl+1, line: line.line }
-            }));
-            lines.push(PyLine { indent_lvl: lvl, line: "):".into() })
-        },
-        PyLines::None => todo!(),
+    let roperand = roperand.unwrap_one().line;
+
+    // Fields were already resolved to self.{name} by the scope
+    let operation = |loperand: &str| match operator {
+        "hexpat_div" => format!("hexpat_div({loperand}, {roperand})"),
+        _ if rparenthesized => format!("{loperand} {operator} ({roperand})"),
+        _ => format!("{loperand} {operator} {roperand}")
+    };
+    let line = match (width, char_bits, operator) {
+        (_, _, "=") => format!("{loperand} = {}", stored(roperand.clone(), &roperand_type, &target_type)),
+        (Some(_), _, _) => format!("{loperand} = {}", stored(operation(&loperand), &InferredType::Unknown, &target_type)),
+        (_, Some(bits), _) => format!("{loperand} = chr(hexpat_wrap({}, {bits}, False))", operation(&format!("ord({loperand})"))),
+        (None, None, "hexpat_div") => format!("{loperand} = {}", operation(&loperand)),
+        (None, None, _) => format!("{loperand} {operator}= {roperand}")
     };
 
-    lines.extend(moperand);
-    lines.push(PyLine { indent_lvl: lvl, line: "else:".into() });
-    lines.extend(roperand);
+    one_py_line(lvl, line)
+}
 
-    PyLines::Multiple(lines)
+pub(crate) fn translate_ternary(loperand: Box<Spanned<Expr>>, moperand: Box<Spanned<Expr>>, roperand: Box<Spanned<Expr>>, lvl: usize, context: &Scope) -> PyLines {
+    // a ? b : c becomes b if a else c. Python groups a ternary in the last operand like hexpat does
+    let lparenthesized = needs_parentheses(&loperand, precedence::TERNARY, true);
+    let mparenthesized = needs_parentheses(&moperand, precedence::TERNARY, true);
+    let rparenthesized = needs_parentheses(&roperand, precedence::TERNARY, false);
+    let loperand = translate_expr(*loperand, lvl, context).unwrap_one().line;
+    let moperand = translate_expr(*moperand, lvl, context).unwrap_one().line;
+    let roperand = translate_expr(*roperand, lvl, context).unwrap_one().line;
+    let loperand = if lparenthesized { format!("({loperand})") } else { loperand };
+    let moperand = if mparenthesized { format!("({moperand})") } else { moperand };
+    let roperand = if rparenthesized { format!("({roperand})") } else { roperand };
+
+    one_py_line(lvl, format!("{moperand} if {loperand} else {roperand}"))
 }
 
-pub(crate) fn translate_call(func_name: Box<Spanned<Expr>>, arguments: Spanned<Vec<Spanned<Expr>>>, lvl: usize, context: StatementsContext) -> PyLines {
-    panic!("Didn't take context into account");
-    let func_name = translate_expr(func_name.0, lvl, context);
-    let func_name = func_name.unwrap_one().line;
+pub(crate) fn translate_call(func_name: Box<Spanned<Expr>>, arguments: Spanned<Vec<Spanned<Expr>>>, lvl: usize, context: &Scope) -> PyLines {
+    let func_name = match &func_name.0 {
+        // They are reserved so nothing else takes their names
+        Expr::Local { name } if is_runtime_function(&name.0) && context.lookup(&name.0).is_none() => name.0.clone(),
+        _ => translate_expr(*func_name, lvl, context).unwrap_one().line
+    };
     let arguments = arguments.0.into_iter()
-        .map(|(expr, _)| translate_expr(expr, lvl, context))
+        .map(|expr| translate_expr(expr, lvl, context))
         .map(|py_line| py_line.unwrap_one().line)
-        .fold(String::new(), |old_str, expr| old_str + ", " + &expr);
+        .collect::<Vec<_>>()
+        .join(", ");
 
     let line = format!("{func_name}({arguments})");
     one_py_line(lvl, line)
 }
 
-pub(crate) fn translate_if(test: Box<Spanned<Expr>>, consequent: Spanned<Vec<Spanned<Statement>>>, lvl: usize, context: StatementsContext) -> PyLines {
-    panic!("Didn't take context into account");
-    let test = translate_expr(test.0, lvl, context);
+pub(crate) fn translate_if(test: Box<Spanned<Expr>>, consequent: Spanned<Vec<Spanned<Statement>>>, lvl: usize, context: &Scope) -> PyLines {
+    let test = translate_expr(*test, lvl, context);
     let test = test.unwrap_one().line;
+    // An if doesn't change what its statements mean, so they keep the context
     let consequent = vec_translate_statements(consequent.0, lvl+1, context);
 
     let mut lines = vec![PyLine { indent_lvl: lvl, line: format!("if {test}:") }];
-    lines.extend(consequent.into_iter());
+    lines.extend(block_or_pass(consequent, lvl+1));
 
     PyLines::Multiple(lines)
 }
 
-pub(crate) fn translate_if_block(ifs: Spanned<Vec<Spanned<Statement>>>, alternative: Spanned<Vec<Spanned<Statement>>>, lvl: usize, context: StatementsContext) -> PyLines {
-    panic!("Didn't take context into account");
-    let ifs = vec_translate_statements(ifs.0, lvl, context);
-    let alternative = vec_translate_statements(alternative.0, lvl+1, context);
-
-    let mut lines = ifs.into_iter()
-        .collect::<Vec<_>>();
+pub(crate) fn translate_if_block(ifs: Spanned<Vec<Spanned<Statement>>>, alternative: Spanned<Vec<Spanned<Statement>>>, lvl: usize, context: &Scope) -> PyLines {
+    let mut lines = Vec::new();
+    for (i, stmnt) in ifs.0.into_iter().enumerate() {
+        let mut if_lines = translate_statement(stmnt, lvl, context).into_iter();
+        if let Some(mut first_line) = if_lines.next() {
+            if i > 0 {
+                first_line.line = format!("el{}", first_line.line);
+            }
+            lines.push(first_line);
+        }
+        lines.extend(if_lines);
+    }
 
-    lines.push(PyLine { indent_lvl: lvl, line: "else:".into() });
-    lines.extend(alternative);
+    if !alternative.0.is_empty() {
+        let alternative = vec_translate_statements(alternative.0, lvl+1, context);
+        lines.push(PyLine { indent_lvl: lvl, line: "else:".into() });
+        lines.extend(alternative);
+    }
 
     PyLines::Multiple(lines)
 }
 
-pub(crate) fn translate_definition(value_type: Spanned<HexTypeDef>, name: Box<Spanned<Expr>>, body: Box<Spanned<Expr>>, lvl: usize, context: StatementsContext) -> PyLines {
+pub(crate) fn translate_definition(value_type: Spanned<HexTypeDef>, name: Box<Spanned<Expr>>, body: Box<Spanned<Expr>>, lvl: usize, context: &Scope) -> PyLines {
+    let big_endian = is_big_endian(&value_type.0);
     let value_type = translate_hextypedef(value_type.0, lvl, context);
-    let value_type = value_type.line;
-    let name = translate_expr(name.0, lvl, context);
-    let name = name.unwrap_one().line;
-    let body = translate_expr(body.0, lvl, context);
-    let body = body.unwrap_one().line;
-
-    let line = match context {
-        StatementsContext::None => format!("{name}: {value_type} = {value_type}() @ ({body})"),
-        StatementsContext::Struct => format!("self.{name}: {value_type} = {value_type}() @ ({body})"),
-        StatementsContext::Function => format!("{name}: {value_type} = {value_type}() @ ({body})"),
-        StatementsContext::Namespace => format!("{name}: {value_type} = {value_type}() @ ({body})"),
-        StatementsContext::Bitfield => todo!(),
-        StatementsContext::WhileLoop => todo!(),
-        StatementsContext::ForLoop => todo!(),
-        StatementsContext::Union => todo!(),
-        StatementsContext::Try => todo!(),
+    let inferred = type_of(name.as_ref());
+    // auto takes the type of its value
+    let value_type = match inferred.python_name() {
+        Some(inferred) if value_type.line == "auto" => inferred,
+        _ => value_type.line
+    };
+    let (body, attribute) = split_attribute(*body);
+    let kind = definition_kind(&name, &body);
+    let owner = context.owner();
+    // The entries of bitfields are bits, which patterns can't be read from
+    if owner == StatementsContext::Bitfield && kind != DefinitionKind::Initialization {
+        return unsupported((name.1.start, body.1.end), "Patterns inside of bitfields aren't supported", lvl)
+    }
+    let hexpat_name = local_name(name.0);
+    let name = mangle(&hexpat_name);
+    let body_type = type_of(&body);
+    let body = match kind {
+        DefinitionKind::Declaration => None,
+        DefinitionKind::Placement | DefinitionKind::Initialization => Some(translate_expr(body, lvl, context).unwrap_one().line),
     };
 
+    let (line, symbol_kind) = match (kind, owner) {
+        (DefinitionKind::Initialization, _) => (local_variable(&name, &value_type, body.map(|body| (body, body_type)), &inferred), SymbolKind::Local),
+        // Strings can't be placed, so they are always plain python variables
+        _ if is_str(&inferred, &value_type) => (local_variable(&name, &value_type, None, &inferred), SymbolKind::Local),
+        // Outside of structs, variables without placement don't read anything
+        (
+            DefinitionKind::Declaration,
+            StatementsContext::None | StatementsContext::Namespace | StatementsContext::Function
+        ) => (local_variable(&name, &value_type, None, &inferred), SymbolKind::Local),
+        (DefinitionKind::Declaration | DefinitionKind::Placement, owner) => {
+            let instance = match owner {
+                StatementsContext::Struct | StatementsContext::Union if !is_builtin_type(&value_type) => format!("{value_type}().set_parent(self)"),
+                _ => format!("{value_type}()"),
+            };
+            // be Flags flags; only bitfields read big endian data differently
+            let instance = match big_endian && !is_builtin_type(&value_type) {
+                true => format!("{instance}.set_big_endian()"),
+                false => instance
+            };
+            let instance = with_hexpat_name(instance, &hexpat_name);
+            let value = with_attributes(format!("{instance} @ {}", placement(body, owner)), attribute, lvl, context);
+            match owner {
+                StatementsContext::None
+                | StatementsContext::Namespace => (format!("{name}: {value_type} = {}", placed(value, &hexpat_name)), SymbolKind::Local),
+                StatementsContext::Function => (format!("{name}: {value_type} = {value}"), SymbolKind::Local),
+                StatementsContext::Struct
+                | StatementsContext::Union => (format!("self.{}: {value_type} = {value}", mangle_field(&hexpat_name)), SymbolKind::Field),
+                StatementsContext::Bitfield => unreachable!("Patterns inside of bitfields were refused above"),
+                StatementsContext::WhileLoop
+                | StatementsContext::ForLoop
+                | StatementsContext::Try
+                | StatementsContext::Enum => unreachable!("Loops, try blocks and enums don't own definitions"),
+            }
+        }
+    };
+    context.define(hexpat_name, Symbol { kind: symbol_kind, value_type });
+
     one_py_line(lvl, line)
 }
 
-pub(crate) fn translate_array_definition(value_type: Spanned<HexTypeDef>, array_name: Box<Spanned<Expr>>, size: Box<Spanned<Expr>>, body: Box<Spanned<Expr>>, lvl: usize, context: StatementsContext) -> PyLines {
+/// `instance.set_name("{hexpat_name}")` if the python name is a different one, so dumps show the hexpat one
+fn with_hexpat_name(instance: String, hexpat_name: &str) -> String {
+    match is_mangled(hexpat_name) {
+        true => format!(r#"{instance}.set_name("{hexpat_name}")"#),
+        false => instance
+    }
+}
+
+/// How a definition gets its value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DefinitionKind {
+    /// `u8 a;`
+    Declaration,
+    /// `u8 a @ 0x10;`
+    Placement,
+    /// `u8 a = 5;`
+    Initialization,
+}
+
+fn definition_kind(name: &Spanned<Expr>, body: &Spanned<Expr>) -> DefinitionKind {
+    if is_empty_expr(&body.0) {
+        return DefinitionKind::Declaration
+    }
+
+    match is_initialization(name, body.1.start) {
+        true => DefinitionKind::Initialization,
+        false => DefinitionKind::Placement
+    }
+}
+
+/// A pattern placed outside of structs, recorded in the order it's placed. Python variables can only hold the last
+/// pattern defined with a name, but like in ImHex every placed one is part of the result
+fn placed(value: String, hexpat_name: &str) -> String {
+    format!(r#"placed(placed_patterns, "{hexpat_name}", {value})"#)
+}
+
+/// `{name}: {value_type} = {value}` for variables that live in python instead of being read from the data
+fn local_variable(name: &str, value_type: &str, value: Option<(String, InferredType)>, inferred: &InferredType) -> String {
+    let value = value.map_or_else(|| default_value(value_type), |(value, body_type)| stored(value, &body_type, inferred));
+    match value_type {
+        "auto" => format!("{name} = {value}"),
+        _ => format!("{name}: {value_type} = {value}")
+    }
+}
+
+/// `value` (of type `value_type`) as a variable of type `target` keeps it. Python ints don't overflow, so the ones with
+/// a fixed width are wrapped by hand, unless `value` is a literal that fits or was already wrapped
+fn stored(value: String, value_type: &InferredType, target: &InferredType) -> String {
+    let Some((bits, signed)) = target.width() else {
+        return value
+    };
+    let signed = if signed { "True" } else { "False" };
+    match value_type {
+        // Already reported by the type inference
+        InferredType::Str | InferredType::Char | InferredType::Char16 => value,
+        _ if literals::fits(&value, bits, signed == "True") || is_wrapped(&value, bits, signed) => value,
+        _ => format!("hexpat_wrap({value}, {bits}, {signed})")
+    }
+}
+
+/// Whether `value` is a single `hexpat_wrap(..., bits, signed)` call
+fn is_wrapped(value: &str, bits: usize, signed: &str) -> bool {
+    let Some(arguments) = value.strip_prefix("hexpat_wrap(") else {
+        return false
+    };
+    if !value.ends_with(&format!(", {bits}, {signed})")) {
+        return false
+    }
+    // hexpat_wrap(a, 8, False) + hexpat_wrap(b, 8, False) ends the same way
+    let mut depth = 1;
+    for (i, c) in arguments.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => ()
+        }
+        if depth == 0 {
+            return i == arguments.len() - 1
+        }
+    }
+    false
+}
+
+/// Whether the translated type is one of rs_hexpyt's primitives, which don't need to know their parent
+fn is_builtin_type(value_type: &str) -> bool {
+    matches!(value_type,
+        "u8" | "u16" | "u24" | "u32" | "u48" | "u64" | "u96" | "u128"
+        | "s8" | "s16" | "s24" | "s32" | "s48" | "s64" | "s96" | "s128"
+        | "Float" | "double" | "char" | "char16" | "Bool" | "str" | "auto"
+    )
+}
+
+/// Name of what a definition defines. Unlike names in expressions, it's never resolved to `self.{name}`
+fn local_name(expr: Expr) -> String {
+    match expr {
+        Expr::Local { name } => name.0,
+        _ => panic!("Expected a name")
+    }
+}
+
+/// Patterns a struct reads, so references to them become `self.{name}` even before they are defined
+fn struct_fields(body: &[Spanned<Statement>], context: &Scope) -> HashMap<String, Symbol> {
+    let mut fields = HashMap::new();
+    for (stmnt, _) in body {
+        match stmnt {
+            Statement::Definition(Definition { value_type, name, body }) => {
+                let (body, _) = split_attribute(body.as_ref().clone());
+                if let (DefinitionKind::Declaration | DefinitionKind::Placement, Expr::Local { name }) = (definition_kind(name, &body), &name.0) {
+                    let value_type = translate_hextypedef(value_type.0.clone(), 0, context).line;
+                    fields.insert(name.0.clone(), Symbol { kind: SymbolKind::Field, value_type });
+                }
+            },
+            Statement::ArrayDefinition { value_type, array_name, .. } => if let Expr::Local { name } = &array_name.0 {
+                let value_type = array_type(&translate_hextypedef(value_type.0.clone(), 0, context).line, &type_of(array_name.as_ref()));
+                fields.insert(name.0.clone(), Symbol { kind: SymbolKind::Field, value_type });
+            },
+            // Conditional fields
+            Statement::If { consequent, .. } => fields.extend(struct_fields(&consequent.0, context)),
+            Statement::IfBlock { ifs, alternative } => {
+                fields.extend(struct_fields(&ifs.0, context));
+                fields.extend(struct_fields(&alternative.0, context));
+            },
+            Statement::WhileLoop { body, .. } | Statement::ForLoop { body, .. } => fields.extend(struct_fields(&body.0, context)),
+            _ => ()
+        }
+    }
+
+    fields
+}
+
+/// Value of a local variable declared without one
+fn default_value(value_type: &str) -> String {
+    match value_type {
+        "u8" | "u16" | "u24" | "u32" | "u48" | "u64" | "u96" | "u128" => "0",
+        "s8" | "s16" | "s24" | "s32" | "s48" | "s64" | "s96" | "s128" => "0",
+        "Float" | "double" => "0.0",
+        "char" => r#""\0""#,
+        "char16" => r#""\0\0""#,
+        "Bool" => "False",
+        "str" => r#""""#,
+        "auto" => "None",
+        custom_type => return format!("{custom_type}()"),
+    }.to_string()
+}
+
+pub(crate) fn translate_array_definition(value_type: Spanned<HexTypeDef>, array_name: Box<Spanned<Expr>>, size: Box<Spanned<Expr>>, body: Box<Spanned<Expr>>, lvl: usize, context: &Scope) -> PyLines {
+    // The entries of bitfields are bits, which patterns can't be read from
+    if context.owner() == StatementsContext::Bitfield {
+        return unsupported((array_name.1.start, body.1.end.max(size.1.end)), "Patterns inside of bitfields aren't supported", lvl)
+    }
     let value_type = translate_hextypedef(value_type.0, lvl, context);
     let value_type = value_type.line;
-    let array_name = translate_expr(array_name.0, lvl, context);
-    let array_name = array_name.unwrap_one().line;
-    let size = translate_expr(size.0, lvl, context);
-    let size = size.unwrap_one().line;
-    let body = translate_expr(body.0, lvl, context);
-    let body = body.unwrap_one().line;
-
-    let lines = match context {
-        StatementsContext::None => vec![
-            PyLine {indent_lvl: lvl, line: format!("{array_name}: Array[{value_type}] = Array({value_type}, {size}) @ ({body})")}
-        ],
-        StatementsContext::Struct => vec![
-            PyLine { indent_lvl: lvl, line: format!("{array_name}: Array[{value_type}] = Array({value_type}, {size}) @ _dollar___offset")},
-            PyLine { indent_lvl: lvl, line: format!("self.{array_name} = {array_name}")}
-        ],
-        StatementsContext::Function => todo!(),
-        StatementsContext::Namespace => todo!(),
-        StatementsContext::Bitfield => todo!(),
-        StatementsContext::WhileLoop => todo!(),
-        StatementsContext::ForLoop => todo!(),
-        StatementsContext::Union => todo!(),
-        StatementsContext::Try => todo!(),
+    let inferred = type_of(array_name.as_ref());
+    let hexpat_name = local_name(array_name.0);
+    let array_name = mangle(&hexpat_name);
+    let size = if is_empty_expr(&size.0) {
+        // u8 a[]; char s[]; null-terminated
+        "None".to_string()
+    } else {
+        translate_expr(*size, lvl, context).unwrap_one().line
+    };
+    let (body, attribute) = split_attribute(*body);
+    let body = if is_empty_expr(&body.0) {
+        None
+    } else {
+        Some(translate_expr(body, lvl, context).unwrap_one().line)
+    };
+
+    let owner = context.owner();
+    let array_type = array_type(&value_type, &inferred);
+    // Character arrays are exposed as python strings
+    let array_class = match is_char_array(&inferred, &value_type) {
+        true => "String",
+        false => "Array"
     };
 
+    let array = match owner {
+        StatementsContext::Struct | StatementsContext::Union if !is_builtin_type(&value_type) => format!("{array_class}({value_type}, {size}).set_parent(self)"),
+        _ => format!("{array_class}({value_type}, {size})"),
+    };
+    let array = with_hexpat_name(array, &hexpat_name);
+    let value = match (owner, body) {
+        // Local array variable, not read from the data
+        (StatementsContext::Function, None) => format!("{array}.zeroed()"),
+        (_, body) => format!("{array} @ {}", placement(body, owner)),
+    };
+    let value = with_attributes(value, attribute, lvl, context);
+
+    let (lines, symbol_kind) = match owner {
+        StatementsContext::None
+        | StatementsContext::Namespace => (vec![
+            PyLine { indent_lvl: lvl, line: format!("{array_name}: {array_type} = {}", placed(value, &hexpat_name)) }
+        ], SymbolKind::Local),
+        StatementsContext::Function => (vec![
+            PyLine { indent_lvl: lvl, line: format!("{array_name}: {array_type} = {value}") }
+        ], SymbolKind::Local),
+        StatementsContext::Struct
+        | StatementsContext::Union => (vec![
+            PyLine { indent_lvl: lvl, line: format!("self.{}: {array_type} = {value}", mangle_field(&hexpat_name)) }
+        ], SymbolKind::Field),
+        StatementsContext::Bitfield => unreachable!("Arrays inside of bitfields are reported"),
+        StatementsContext::WhileLoop
+        | StatementsContext::ForLoop
+        | StatementsContext::Try
+        | StatementsContext::Enum => unreachable!("Loops, try blocks and enums don't own definitions"),
+    };
+    context.define(hexpat_name, Symbol { kind: symbol_kind, value_type: array_type });
+
     unkown_py_lines(lines)
 }
 
-pub(crate) fn translate_bitfield_entry(name: Spanned<String>, length: Box<Spanned<Expr>>, lvl: usize, context: StatementsContext) -> PyLines {
-    panic!("Didn't take context into account");
-    let name = name.0;
-    let length = translate_expr(length.0, lvl, context).unwrap_one().line;
+/// Python type of an array of `value_type`, whose inferred type is `inferred`. Character arrays are exposed as python strings
+fn array_type(value_type: &str, inferred: &InferredType) -> String {
+    match is_char_array(inferred, value_type) {
+        true => "String".to_string(),
+        false => format!("Array[{value_type}]")
+    }
+}
 
-    one_py_line(lvl, format!("{name}: {length}"))
+/// Whether an array is of chars, aliases like `using Ch = char;` included.
+/// Types of statements from included files aren't inferred, for those only the written type is known
+fn is_char_array(inferred: &InferredType, value_type: &str) -> bool {
+    match inferred {
+        InferredType::Array(element) => matches!(**element, InferredType::Char | InferredType::Char16),
+        InferredType::Unknown => matches!(value_type, "char" | "char16"),
+        _ => false
+    }
 }
 
-pub(crate) fn translate_enum_entry(name: Spanned<String>, value: Box<Spanned<Expr>>, lvl: usize, context: StatementsContext) -> PyLines {
-    panic!("Didn't take context into account");
-    let name = name.0;
-    let value = translate_expr(value.0, lvl, context).unwrap_one().line;
+/// Whether a variable is a string, aliases included. Like `is_char_array`, for statements from included files only the
+/// written type is known
+fn is_str(inferred: &InferredType, value_type: &str) -> bool {
+    match inferred {
+        InferredType::Unknown => value_type == "str",
+        inferred => *inferred == InferredType::Str
+    }
+}
 
-    one_py_line(lvl, format!("{name} = {value}"))
+/// Where a pattern is read: at its placement (`@ 0x10`) if it has one, otherwise at `$`.
+/// Placed patterns don't move `$`
+fn placement(body: Option<String>, owner: StatementsContext) -> String {
+    let dollar = generated(DOLLAR);
+    match (owner, body) {
+        (StatementsContext::Struct | StatementsContext::Union, Some(body)) => format!("Dollar({body}, {dollar}.byts)"),
+        (_, Some(body)) => format!("Dollar({body}, {})", generated(BYTES)),
+        // Every member of a union starts at the same offset
+        (StatementsContext::Union, None) => format!("{}.copy()", generated(DOLLAR_COPY)),
+        (_, None) => dollar,
+    }
 }
 
-pub(crate) fn translate_namespace_access(previous: Box<Spanned<Expr>>, name: Spanned<String>, lvl: usize, context: StatementsContext) -> PyLines {
-    panic!("Didn't take context into account");
-    let name = name.0;
-    let previous = translate_expr(previous.0, lvl, context).unwrap_one().line;
+pub(crate) fn translate_bitfield_entry(name: Spanned<String>, length: Box<Spanned<Expr>>, span: (usize, usize), lvl: usize, context: &Scope) -> PyLines {
+    if context.owner() != StatementsContext::Bitfield {
+        return unsupported(span, "Bitfield entries are only allowed inside of bitfields", lvl)
+    }
+    let entry_type = bitfield_entry_type(span, &name);
+    let hexpat_name = name.0;
+    // Lengths can be any expression, so the bits are read at runtime
+    let length = translate_expr(*length, lvl, context).unwrap_one().line;
+    let dollar = generated(DOLLAR);
+    let bits = match entry_type.as_deref() {
+        None | Some("unsigned") => format!("self.read_bits({dollar}, {length})"),
+        Some("signed") => format!("self.read_bits({dollar}, {length}, signed=True)"),
+        Some(entry_type) => format!("self.read_bits({dollar}, {length}, {})", source_type(entry_type)),
+    };
 
-    one_py_line(lvl, format!("{previous}.{name}"))
+    let line = match hexpat_name.as_str() {
+        // padding : 3;
+        "padding" => bits,
+        _ => format!("self.{} = {}", mangle_field(&hexpat_name), with_hexpat_name(bits, &hexpat_name))
+    };
+
+    one_py_line(lvl, line)
 }
 
-pub(crate) fn translate_using(new_name: Spanned<String>, template_parameters: Vec<Spanned<Expr>>, old_name: Spanned<HexTypeDef>, lvl: usize, context: StatementsContext) -> PyLines {
-    panic!("Didn't take context into account");
-    let new_name = new_name.0;
-    let old_name = translate_hextypedef(old_name.0, lvl, context).line;
+/// Type written before the name of a bitfield entry (`signed a : 4;`, `Kind k : 2;`). None for plain entries.
+/// The parser only keeps the name and the length, so it's read from the tokens of the entry before its name
+fn bitfield_entry_type((start, _): (usize, usize), name: &Spanned<String>) -> Option<String> {
+    // Entries from included files
+    if !is_spelled(name.1.start, name.1.end, &name.0) {
+        return None
+    }
+    let tokens = tokens_between(start, name.1.start);
+    let is_type = !tokens.is_empty() && tokens.iter().all(|token| token.kind == TokenKind::Name || token.is(":"));
 
-    let line = if template_parameters.is_empty() {
-        format!("{new_name} = {old_name}")
-    } else {
-        let template_parameters = vec_translate_exprs(template_parameters, lvl, context).unwrap_one().line;
+    match is_type {
+        true => Some(tokens_text(&tokens)),
+        false => None
+    }
+}
+
+/// Python for a type written in the source, for types that the parser doesn't keep
+fn source_type(hexpat_type: &str) -> String {
+    match hexpat_type {
+        "bool" => "Bool".to_string(),
+        "float" => "Float".to_string(),
+        "str" => "str".to_string(),
+        _ => mangle_path(hexpat_type)
+    }
+}
 
-        format!("{new_name} = {old_name}<{template_parameters}>")
+/// Names of the entries of a bitfield, so references to them become `self.{name}`
+fn bitfield_fields(body: &[Spanned<Statement>]) -> HashMap<String, Symbol> {
+    let mut fields = HashMap::new();
+    for (stmnt, span) in body {
+        match stmnt {
+            Statement::BitFieldEntry { name, .. } if name.0 != "padding" => {
+                let value_type = bitfield_entry_type((span.start, span.end), name)
+                    .filter(|entry_type| entry_type != "signed" && entry_type != "unsigned")
+                    .map_or("BitFieldEntry".to_string(), |entry_type| source_type(&entry_type));
+                fields.insert(name.0.clone(), Symbol { kind: SymbolKind::Field, value_type });
+            },
+            // Conditional entries
+            Statement::If { consequent, .. } => fields.extend(bitfield_fields(&consequent.0)),
+            Statement::IfBlock { ifs, alternative } => {
+                fields.extend(bitfield_fields(&ifs.0));
+                fields.extend(bitfield_fields(&alternative.0));
+            },
+            _ => ()
+        }
+    }
+
+    fields
+}
+
+pub(crate) fn translate_enum_entry(name: Spanned<String>, value: Box<Spanned<Expr>>, lvl: usize, context: &Scope) -> PyLines {
+    unreachable!("Enum entries depend on the previous ones, so they are translated by translate_enum")
+}
+
+pub(crate) fn translate_namespace_access(previous: Box<Spanned<Expr>>, name: Spanned<String>, lvl: usize, context: &Scope) -> PyLines {
+    // Kind::Image, my::Kind::Image
+    let previous_path = qualified_path(expr_hexpat_path(&previous.0).iter().map(String::as_str), context);
+    let name = match context.is_enum(&previous_path) {
+        true => mangle_entry(&name.0),
+        false => mangle(&name.0)
     };
+    let previous = translate_expr(*previous, lvl, context).unwrap_one().line;
 
-    one_py_line(lvl, line)
+    one_py_line(lvl, format!("{previous}.{name}"))
+}
+
+pub(crate) fn translate_using(new_name: Spanned<String>, template_parameters: Vec<Spanned<Expr>>, old_name: Spanned<HexTypeDef>, lvl: usize, context: &Scope) -> PyLines {
+    if !template_parameters.is_empty() {
+        return unsupported((new_name.1.start, old_name.1.end), "Templates aren't supported", lvl)
+    }
+    let hexpat_name = new_name.0;
+    let new_name = mangle(&hexpat_name);
+    // An alias of a custom type has its whole path, so it's looked up like the type it names
+    let old_path = match &old_name.0.name.0 {
+        HexType::Custom(path) => Some(qualified_path(path.split("::"), context)),
+        HexType::Path(path) => Some(qualified_path(path.iter().map(String::as_str), context)),
+        HexType::V(_) => None
+    };
+    let old_name = translate_hextypedef(old_name.0, lvl, context).line;
+    match old_path {
+        Some(old_path) => context.define(hexpat_name, Symbol { kind: SymbolKind::Type, value_type: old_path }),
+        None => define_type(hexpat_name, &new_name, SymbolKind::Type, context),
+    }
+
+    one_py_line(lvl, format!("{new_name} = {old_name}"))
 }
 
-pub(crate) fn translate_return(value: Box<Spanned<Expr>>, lvl: usize, context: StatementsContext) -> PyLines {
-    panic!("Didn't take context into account");
-    let value = translate_expr(value.0, lvl, context).unwrap_one().line;
+pub(crate) fn translate_return(value: Box<Spanned<Expr>>, lvl: usize, context: &Scope) -> PyLines {
+    // return; has no value
+    if is_empty_expr(&value.0) {
+        return one_py_line(lvl, "return".to_string())
+    }
+    let value = translate_expr(*value, lvl, context).unwrap_one().line;
 
     let line = format!("return {value}");
     one_py_line(lvl, line)
 }
 
-pub(crate) fn translate_func(name: Spanned<String>, args: Spanned<Vec<Spanned<FuncArgument>>>, body: Spanned<Vec<Spanned<Statement>>>, lvl: usize, context: StatementsContext) -> PyLines {
-    if context != StatementsContext::None {
-        panic!("Didn't take context into account");
+pub(crate) fn translate_func(name: Spanned<String>, args: Spanned<Vec<Spanned<FuncArgument>>>, body: Spanned<Vec<Spanned<Statement>>>, lvl: usize, context: &Scope) -> PyLines {
+    if !is_global(context) {
+        return unsupported((name.1.start, body.1.end), "Functions are only allowed on global", lvl)
     }
-    let name = name.0;
+    let hexpat_name = name.0;
+    let name = mangle(&hexpat_name);
+    define_type(hexpat_name, &name, SymbolKind::Type, context);
+    let function_context = context.push(StatementsContext::Function, HashMap::new());
     let args = args.0.into_iter()
-        .map(|arg| translate_arg(arg.0, lvl, context))
-        .map(|arg| arg.line)
-        .fold(String::new(), |old, new| format!("{old}, {new}"));
-    let body = vec_translate_statements(body.0, lvl+1, StatementsContext::Function);
+        .map(|arg| translate_arg(arg.0, lvl, &function_context).line)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let body = vec_translate_statements(body.0, lvl+1, &function_context);
 
     let mut lines = vec![
         PyLine{ indent_lvl: lvl, line: format!("def {name}({args}):") }
     ];
+    lines.extend(global_declaration(&function_context, lvl+1));
 
     lines.extend(body.into_iter());
 
     PyLines::Multiple(lines)
 }
 
-pub(crate) fn translate_struct(name: Spanned<String>, body: Spanned<Vec<Spanned<Statement>>>, template_parameters: Vec<Spanned<Expr>>, lvl: usize, context: StatementsContext) -> PyLines {
-    if context != StatementsContext::None {
-        panic!("Structs only allowed on global")
+pub(crate) fn translate_struct(name: Spanned<String>, body: Spanned<Vec<Spanned<Statement>>>, template_parameters: Vec<Spanned<Expr>>, lvl: usize, context: &Scope) -> PyLines {
+    if !is_global(context) {
+        return unsupported((name.1.start, body.1.end), "Structs are only allowed on global", lvl)
+    }
+    if let (Some(first), Some(last)) = (template_parameters.first(), template_parameters.last()) {
+        define_type(name.0.clone(), &mangle(&name.0), SymbolKind::Type, context);
+        return unsupported((first.1.start, last.1.end), "Templates aren't supported", lvl)
     }
-    let name = name.0;
-    let body = vec_translate_statements(body.0, lvl+2, StatementsContext::Struct);
+    let parents = struct_parents(&name, body.1.start);
+    report_type_attributes(&trailing_attributes(&name, body.1.end), &[]);
+    // Their tokens are in another file, so what's between the name and the body can't be read
+    if !is_spelled(name.1.start, name.1.end, &name.0) && body.1.start > name.1.end + " {".len() {
+        report((name.1.start, name.1.end), "The parents of structs from included files aren't read");
+    }
+    let hexpat_name = name.0;
+    let name = mangle(&hexpat_name);
+    define_type(hexpat_name, &name, SymbolKind::Type, context);
+    // struct B : A {...}; A's fields are read first and B has them too. Unknown parents are left out
+    let mut fields = HashMap::new();
+    let mut known_parents = Vec::new();
+    let mut unknown_parents = Vec::new();
+    for (parent, span) in parents {
+        let parent_path = qualified_path(parent.split("::"), context);
+        match context.struct_fields(&parent_path) {
+            Some(parent_fields) => {
+                fields.extend(parent_fields);
+                known_parents.push(parent);
+            },
+            None => unknown_parents.push((parent, span))
+        }
+    }
+    fields.extend(struct_fields(&body.0, context));
+    context.define_struct(context.qualified(&name), fields.clone());
+    let struct_context = context.push(StatementsContext::Struct, fields);
+    let body = vec_translate_statements(body.0, lvl+2, &struct_context);
+
+    // The bases are in the body of the namespace, and __matmul__ is a function that only sees whole paths
+    let bases = known_parents.iter().map(|parent| resolve_path(parent.split("::"), context)).collect::<Vec<_>>();
+    let mut lines = struct_class_header(&name, "struct", &bases, lvl);
+    lines.extend(global_declaration(&struct_context, lvl+2));
+
+    let dollar = generated(DOLLAR);
+    lines.extend(known_parents.iter().map(|parent| PyLine {
+        indent_lvl: lvl+2,
+        line: format!("{}.__matmul__(self, {dollar})", resolve_path(parent.split("::"), &struct_context))
+    }));
+    for (parent, span) in unknown_parents {
+        lines.extend(unsupported(span, &format!("{parent} isn't a struct or union"), lvl+2));
+    }
+    lines.extend(body.into_iter());
+
+    lines.push(PyLine { indent_lvl: 0, line: String::new() });
+    lines.extend(struct_end(StatementsContext::Struct, lvl+2));
 
+    PyLines::Multiple(lines)
+}
+
+/// Python makes every name a function assigns local to it, unless it's declared global
+fn global_declaration(context: &Scope, lvl: usize) -> Option<PyLine> {
+    let assigned_globals = context.assigned_globals();
+    match assigned_globals.is_empty() {
+        true => None,
+        false => Some(PyLine { indent_lvl: lvl, line: format!("global {}", assigned_globals.join(", ")) })
+    }
+}
+
+/// End of the `__matmul__` of a struct or union, after its fields were read
+fn struct_end(kind: StatementsContext, lvl: usize) -> Vec<PyLine> {
+    let (dollar, dollar_copy) = (generated(DOLLAR), generated(DOLLAR_COPY));
+    match kind {
+        StatementsContext::Struct => vec![
+            PyLine { indent_lvl: lvl, line: format!(r#"super().init_struct({dollar_copy}, {dollar}.copy())"#) },
+            PyLine { indent_lvl: lvl, line: r#"return self"#.into() },
+        ],
+        // $ ends after the biggest member
+        StatementsContext::Union => vec![
+            PyLine { indent_lvl: lvl, line: format!(r#"super().init_union({dollar_copy})"#) },
+            PyLine { indent_lvl: lvl, line: format!(r#"{dollar} += self.size()"#) },
+            PyLine { indent_lvl: lvl, line: r#"return self"#.into() },
+        ],
+        _ => unreachable!("Only structs and unions are read by __matmul__")
+    }
+}
+
+/// Class definition, `__init__` and the start of `__matmul__` of a struct or union
+/// Classes of the structs it inherits are its bases, or Struct if it doesn't inherit any
+fn struct_class_header(name: &str, kind: &str, parents: &[String], lvl: usize) -> Vec<PyLine> {
     /*
     class {name}(Struct):
         """//TODO: This comment is not done yet
@@ -329,7 +896,7 @@ pub(crate) fn translate_struct(name: Spanned<String>, body: Spanned<Vec<Spanned<
     ```"""
         def __init__(self, name: str=""):
             """
-            struct
+            {kind}
 
             Args:
                 name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
@@ -343,109 +910,168 @@ pub(crate) fn translate_struct(name: Spanned<String>, body: Spanned<Vec<Spanned<
                 _dollar___offset = _dollar___offset.to_dollar()
             _dollar___offset_copy = _dollar___offset.copy()
 */
-    let mut lines = if template_parameters.is_empty() {
-        vec![
-            PyLine{ indent_lvl: lvl, line: format!("class {name}(Struct):") },
-        ]
-    } else {
-        let template_parameters = vec_translate_exprs(template_parameters, lvl, context).unwrap_one().line;
-
-        vec![
-            PyLine{ indent_lvl: lvl, line: format!("class {name}(Struct):#<{template_parameters}>") }
-        ]
+    let bases = match parents {
+        [] => "Struct".to_string(),
+        parents => parents.join(", ")
     };
+    let mut lines = vec![
+        PyLine{ indent_lvl: lvl, line: format!("class {name}({bases}):") },
+    ];
 
+    let (dollar, dollar_copy) = (generated(DOLLAR), generated(DOLLAR_COPY));
     lines.extend(vec![
         PyLine{ indent_lvl: lvl+1, line: r#"def __init__(self, name: str=""):"#.into() },
         PyLine{ indent_lvl: lvl+2, line: r#"""""#.into() },
-        PyLine{ indent_lvl: lvl+2, line: r#"struct"#.into() },
+        PyLine{ indent_lvl: lvl+2, line: kind.into() },
         PyLine{ indent_lvl: 0, line: r#""#.into() },
         PyLine{ indent_lvl: lvl+2, line: r#"Args"#.into() },
         PyLine{ indent_lvl: lvl+3, line: r#"name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to ""."#.into() },
         PyLine{ indent_lvl: lvl+2, line: r#"""""#.into() },
         PyLine{ indent_lvl: lvl+2, line: r#"super().__init__(name)"#.into() },
-        PyLine{ indent_lvl: lvl+1, line: r#"def __matmul__(self, _dollar___offset):"#.into() },
+        PyLine{ indent_lvl: lvl+1, line: format!(r#"def __matmul__(self, {dollar}):"#) },
 
-        PyLine{ indent_lvl: lvl+2, line: r#"if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):"#.into() },
-        PyLine{ indent_lvl: lvl+3, line: r#"raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {{type(_dollar___offset)}} was used instead')"#.into() },
-        PyLine{ indent_lvl: lvl+2, line: r#"if isinstance(_dollar___offset, IntStruct):"#.into() },
-        PyLine{ indent_lvl: lvl+3, line: r#"_dollar___offset = _dollar___offset.to_dollar()"#.into() },
-        PyLine{ indent_lvl: lvl+2, line: r#"_dollar___offset_copy = _dollar___offset.copy()"#.into() },
+        PyLine{ indent_lvl: lvl+2, line: format!(r#"if not (isinstance({dollar}, Dollar) or isinstance({dollar}, IntStruct)):"#) },
+        PyLine{ indent_lvl: lvl+3, line: format!(r#"raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {{{{type({dollar})}}}} was used instead')"#) },
+        PyLine{ indent_lvl: lvl+2, line: format!(r#"if isinstance({dollar}, IntStruct):"#) },
+        PyLine{ indent_lvl: lvl+3, line: format!(r#"{dollar} = {dollar}.to_dollar()"#) },
+        PyLine{ indent_lvl: lvl+2, line: format!(r#"{dollar_copy} = {dollar}.copy()"#) },
     ]);
 
-    lines.extend(body.into_iter());
-
-    lines.push(PyLine { indent_lvl: lvl+2, line: r#"super().init_struct(_dollar___offset_copy, _dollar___offset.copy())"#.into() });
-    lines.push(PyLine { indent_lvl: lvl+2, line: r#"return self"#.into() });
-
-    PyLines::Multiple(lines)
+    lines
 }
 
-pub(crate) fn translate_namespace(name: Box<Spanned<Expr>>, body: Spanned<Vec<Spanned<Statement>>>, lvl: usize, context: StatementsContext) -> PyLines {
-    panic!("Didn't take context into account");
-    let name = translate_expr(name.0, lvl, context).unwrap_one().line;
-    let body = vec_translate_statements(body.0, lvl, StatementsContext::Namespace);
+pub(crate) fn translate_namespace(name: Box<Spanned<Expr>>, body: Spanned<Vec<Spanned<Statement>>>, lvl: usize, context: &Scope) -> PyLines {
+    if !is_global(context) {
+        return unsupported((name.1.start, body.1.end), "Namespaces are only allowed on global", lvl)
+    }
+    // namespace a::b {} becomes one class per path segment
+    let hexpat_path = expr_hexpat_path(&name.0);
+    let path = expr_path(name.0);
+    let body_lvl = lvl + path.len();
+    define_type(hexpat_path[0].clone(), &path[0], SymbolKind::Type, context);
+    let body = vec_translate_statements(body.0, body_lvl, &context.push_namespace(context.qualified(&path.join("."))));
 
-    let mut lines = vec![
-        PyLine{ indent_lvl: lvl, line: name}
-    ];
+    /*
+    class {name}(namespace(locals(), "{name}")):
+    */
+    let mut lines = path.into_iter()
+        .enumerate()
+        .map(|(depth, name)| PyLine {
+            indent_lvl: lvl+depth,
+            line: format!(r#"class {name}(namespace(locals(), "{name}")):"#)
+        }).collect::<Vec<_>>();
 
-    lines.extend(body.into_iter());
+    lines.extend(block_or_pass(body, body_lvl));
 
     PyLines::Multiple(lines)
 }
 
-enum EnumType {
-    Enum,
-    IntEnum,
-    RealEnum,
-    CharEnum,
-    BoolEnum
+/// Segments of a (possibly qualified) name, `a::b::c` -> `["a", "b", "c"]`
+fn expr_path(expr: Expr) -> Vec<String> {
+    expr_hexpat_path(&expr).iter().map(|segment| mangle(segment)).collect()
 }
 
-impl ToString for EnumType {
-    fn to_string(&self) -> String {
-        match self {
-            EnumType::Enum => "Enum",
-            EnumType::IntEnum => "IntEnum",
-            EnumType::RealEnum => "RealEnum",
-            EnumType::CharEnum => "CharEnum",
-            EnumType::BoolEnum => "BoolEnum",
-        }.into()
+/// Segments of a (possibly qualified) name as written in hexpat
+fn expr_hexpat_path(expr: &Expr) -> Vec<String> {
+    match expr {
+        Expr::Local { name } => vec![name.0.clone()],
+        Expr::NamespaceAccess { previous, name } => {
+            let mut path = expr_hexpat_path(&previous.0);
+            path.push(name.0.clone());
+            path
+        },
+        _ => panic!("Expected a name or a path")
     }
 }
 
-pub(crate) fn translate_enum(name: Spanned<String>, value_type: Spanned<HexTypeDef>, body: Spanned<Vec<Spanned<Expr>>>, lvl: usize, context: StatementsContext) -> PyLines {
-    if context != StatementsContext::None {
-        panic!("Enums only allowed on global")
+/// Defines a type, function or namespace in the current scope with its python path, which functions need to refer to it
+fn define_type(hexpat_name: String, python_name: &str, kind: SymbolKind, context: &Scope) {
+    context.define(hexpat_name, Symbol { kind, value_type: context.qualified(python_name) });
+}
+
+/// Whether type, function and namespace definitions can be made in this context
+fn is_global(context: &Scope) -> bool {
+    matches!(context.kind(), StatementsContext::None | StatementsContext::Namespace)
+}
+
+pub(crate) fn translate_enum(name: Spanned<String>, value_type: Spanned<HexTypeDef>, body: Spanned<Vec<Spanned<Expr>>>, lvl: usize, context: &Scope) -> PyLines {
+    if !is_global(context) {
+        return unsupported((name.1.start, body.1.end), "Enums are only allowed on global", lvl)
     }
-    let name = name.0;
+    let attributes = trailing_attributes(&name, body.1.end);
+    report_type_attributes(&attributes, &["flags"]);
+    let is_flags = attributes.iter().any(|(attribute, _, _)| attribute == "flags");
+    let hexpat_name = name.0;
+    let name = mangle(&hexpat_name);
     let value_type = translate_hextypedef(value_type.0, lvl, context).line;
-    let class = match value_type.as_str() {
-        "u8" | "u16" | "u24" | "u32" | "u48" | "u64" | "u96" | "u128" => EnumType::IntEnum,
-        "s8" | "s16" | "s24" | "s32" | "s48" | "s64" | "s96" | "s128" => EnumType::IntEnum,
-        "Float" | "double" => EnumType::RealEnum,
-        "char" | "char16" => EnumType::CharEnum,
-        "Bool" => EnumType::BoolEnum,
-        _ => EnumType::Enum
-    }.to_string();
-    let body = vec_translate_exprs(body.0, lvl+1, context);
 
     /*
-    class {name}({class}):
-        """ //TODO: This comment is not done yet
-    hexpat definition:
-    ```hexpat
-    {docstring}
-    ```"""
-        _enum__dict___ = {
+    class {name}(Enum):
+        A = 0
+        B = A + 1
+        C = 10
+        _enum__dict___ = {A: "A", B: "B", C: "C"}
+        _enum__ranges___ = [(10, 20, "C")]
     */
     let mut lines = vec![
-        PyLine { indent_lvl: lvl, line: format!("class {name}({class}):") },
-        PyLine { indent_lvl: lvl+1, line: format!("_enum__dict___ = {{") }
+        PyLine { indent_lvl: lvl, line: format!("class {name}(Enum):") },
     ];
 
-    lines.extend(body);
+    // Later entries can use the earlier ones
+    let entries_context = context.push(StatementsContext::Enum, HashMap::new());
+    let mut entry_names = Vec::new();
+    let mut ranges = Vec::new();
+    // Entries without a value are one more than the previous entry (or the end of its range)
+    let mut next_value = "0".to_string();
+    for (entry, _) in body.0 {
+        let (hexpat_name, value) = match entry {
+            Expr::EnumEntry { name, value } => (name.0, value),
+            _ => unreachable!("Enums only have entries")
+        };
+        let entry_name = mangle_entry(&hexpat_name);
+        let (value, value_span) = *value;
+        let value = match value {
+            value if is_empty_expr(&value) => std::mem::replace(&mut next_value, format!("{entry_name} + 1")),
+            // A = 0 ... 9
+            Expr::ExprList { mut list } if list.len() == 2 => {
+                let end = translate_expr(list.pop().unwrap(), lvl+1, &entries_context).unwrap_one().line;
+                let start = translate_expr(list.pop().unwrap(), lvl+1, &entries_context).unwrap_one().line;
+                next_value = format!("{} + 1", parenthesized(&end));
+                ranges.push(format!(r#"({start}, {end}, "{hexpat_name}")"#));
+                start
+            },
+            value => {
+                next_value = format!("{entry_name} + 1");
+                translate_expr((value, value_span), lvl+1, &entries_context).unwrap_one().line
+            },
+        };
+        entries_context.define(hexpat_name.clone(), Symbol { kind: SymbolKind::Entry, value_type: context.qualified(&name) });
+
+        lines.push(PyLine { indent_lvl: lvl+1, line: format!("{entry_name} = {value}") });
+        entry_names.push((hexpat_name, entry_name));
+    }
+
+    let entries_dict = entry_names.iter()
+        .map(|(hexpat_name, entry_name)| format!(r#"{entry_name}: "{hexpat_name}""#))
+        .collect::<Vec<_>>()
+        .join(", ");
+    lines.push(PyLine { indent_lvl: lvl+1, line: format!("{ENUM_DICT} = {{{entries_dict}}}") });
+    // Members keep the hexpat names, the class attributes are the python ones
+    let python_names = entry_names.iter()
+        .filter(|(hexpat_name, entry_name)| hexpat_name != entry_name)
+        .map(|(hexpat_name, entry_name)| format!(r#""{hexpat_name}": "{entry_name}""#))
+        .collect::<Vec<_>>();
+    if !python_names.is_empty() {
+        lines.push(PyLine { indent_lvl: lvl+1, line: format!("{ENUM_PYTHON_NAMES} = {{{}}}", python_names.join(", ")) });
+    }
+    if !ranges.is_empty() {
+        lines.push(PyLine { indent_lvl: lvl+1, line: format!("{ENUM_RANGES} = [{}]", ranges.join(", ")) });
+    }
+    // enum A : u8 {...} [[flags]]; Members becomes an IntFlag instead of an IntEnum
+    if is_flags {
+        lines.push(PyLine { indent_lvl: lvl+1, line: format!("{ENUM_FLAGS} = True") });
+    }
+
     /*
         def __init__(self, value=None, name: str=""):
             """
@@ -455,27 +1081,111 @@ pub(crate) fn translate_enum(name: Spanned<String>, value_type: Spanned<HexTypeD
                 value (optional): Defaults to None.
                 name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to "".
             """
-            super().__init__({desc[1]}, value, name)
+            super().__init__({value_type}, value, name)
     */
+    define_type(hexpat_name, &name, SymbolKind::Enum, context);
+    context.define_enum(context.qualified(&name));
     lines.extend(vec![
         PyLine { indent_lvl: lvl+1, line: format!(r#"def __init__(self, value=None, name: str=""):"#) },
+        PyLine { indent_lvl: lvl+2, line: format!(r#"""""#) },
         PyLine { indent_lvl: lvl+2, line: format!(r#"enum"#) },
         PyLine { indent_lvl: 0, line: format!(r#""#) },
+        PyLine { indent_lvl: lvl+2, line: format!(r#"Args:"#) },
         PyLine { indent_lvl: lvl+3, line: format!(r#"value (optional): Defaults to None."#) },
         PyLine { indent_lvl: lvl+3, line: format!(r#"name (str, optional): The name of this instance. Can be whatever you want or just an empty string. Defaults to ""."#) },
         PyLine { indent_lvl: lvl+2, line: format!(r#"""""#) },
         PyLine { indent_lvl: lvl+2, line: format!(r#"super().__init__({value_type}, value, name)"#) }
-
     ]);
 
     PyLines::Multiple(lines)
 }
 
-pub(crate) fn translate_bitfield(name: Spanned<String>, body: Spanned<Vec<Spanned<Statement>>>, lvl: usize, context: StatementsContext) -> PyLines {
-    if context != StatementsContext::None {
-        panic!("Enums only allowed on global")
+/// Attributes of a type definition and their arguments, `struct A {...} [[inline, color("FF0000")]];` -> `[("inline", []), ("color", ["\"FF0000\""])]`.
+/// The parser doesn't keep them, so they are read from the tokens after the end of the body
+fn trailing_attributes(name: &Spanned<String>, body_end: usize) -> Vec<(String, Vec<String>, (usize, usize))> {
+    // Definitions from included files
+    if !is_spelled(name.1.start, name.1.end, &name.0) {
+        return Vec::new()
+    }
+    let tokens = tokens_from(body_end);
+    let tokens = &tokens[..tokens.iter().position(|token| token.is(";")).unwrap_or(tokens.len())];
+    let start = tokens.windows(2).position(|pair| pair[0].is("[") && pair[1].is("["));
+    let end = tokens.windows(2).rposition(|pair| pair[0].is("]") && pair[1].is("]"));
+    let attributes = match (start, end) {
+        (Some(start), Some(end)) if start+2 <= end => &tokens[start+2..end],
+        _ => return Vec::new()
+    };
+
+    split_top_level(attributes).into_iter()
+        .filter_map(|attribute| match attribute {
+            [name, open, arguments @ .., close] if open.is("(") && close.is(")") => Some((
+                name.text.clone(),
+                split_top_level(arguments).into_iter().map(tokens_text).collect(),
+                (name.start, close.end)
+            )),
+            [name] => Some((name.text.clone(), Vec::new(), (name.start, name.end))),
+            _ => None
+        })
+        .collect()
+}
+
+/// Reports the attributes of a type that aren't among the `translated` ones
+fn report_type_attributes(attributes: &[(String, Vec<String>, (usize, usize))], translated: &[&str]) {
+    for (attribute, _, span) in attributes {
+        if !translated.contains(&attribute.as_str()) {
+            report(*span, &format!("The {attribute} attribute of types isn't supported"));
+        }
+    }
+}
+
+/// Splits on the commas that aren't inside of parentheses
+fn split_top_level(tokens: &[Token]) -> Vec<&[Token]> {
+    let mut pieces = Vec::new();
+    let mut depth = 0;
+    let mut piece_start = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        match token.text.as_str() {
+            "(" | "[" => depth += 1,
+            ")" | "]" => depth -= 1,
+            "," if depth == 0 => {
+                pieces.push(&tokens[piece_start..i]);
+                piece_start = i+1;
+            },
+            _ => ()
+        }
+    }
+    pieces.push(&tokens[piece_start..]);
+
+    pieces.into_iter().filter(|piece| !piece.is_empty()).collect()
+}
+
+/// Text of the tokens without what was between them, `BitfieldOrder :: LeftToRight` -> `BitfieldOrder::LeftToRight`
+fn tokens_text(tokens: &[Token]) -> String {
+    tokens.iter().map(|token| token.text.as_str()).collect()
+}
+
+/// `expr` in parentheses, unless it's a single literal or name
+fn parenthesized(expr: &str) -> String {
+    if expr.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '.') {
+        expr.to_string()
+    } else {
+        format!("({expr})")
+    }
+}
+
+pub(crate) fn translate_bitfield(name: Spanned<String>, body: Spanned<Vec<Spanned<Statement>>>, lvl: usize, context: &Scope) -> PyLines {
+    if !is_global(context) {
+        return unsupported((name.1.start, body.1.end), "Bitfields are only allowed on global", lvl)
     }
-    let name = name.0;
+    let attributes = trailing_attributes(&name, body.1.end);
+    report_type_attributes(&attributes, &["bitfield_order"]);
+    let order = attributes.into_iter()
+        .find(|(attribute, _, _)| attribute == "bitfield_order")
+        .map(|(_, arguments, _)| arguments);
+    let hexpat_name = name.0;
+    let name = mangle(&hexpat_name);
+    define_type(hexpat_name, &name, SymbolKind::Type, context);
+    let (dollar, dollar_copy) = (generated(DOLLAR), generated(DOLLAR_COPY));
 
     /*
     class {name}(BitField):
@@ -500,10 +1210,36 @@ pub(crate) fn translate_bitfield(name: Spanned<String>, body: Spanned<Vec<Spanne
             if isinstance(_dollar___offset, IntStruct):
                 _dollar___offset = _dollar___offset.to_dollar()
             _dollar___offset_copy = _dollar___offset.copy()
-            _read__able____bytes: bytes = _dollar___offset.read({total_bytes})
     */
     let mut lines = vec![
         PyLine { indent_lvl: lvl, line: format!(r#"class {name}(BitField):"#) },
+    ];
+
+    // [[bitfield_order(BitfieldOrder::MostToLeastSignificant, 16)]]
+    if let Some(order) = order {
+        let msb_first = match order.first().and_then(|order| order.rsplit("::").next()) {
+            Some("MostToLeastSignificant" | "LeftToRight") => "True",
+            Some("LeastToMostSignificant" | "RightToLeft") => "False",
+            order => {
+                refuse(format!("Unknown bitfield order {order:?}"));
+                "False"
+            }
+        };
+        lines.push(PyLine { indent_lvl: lvl+1, line: format!("{BITFIELD_MSB_FIRST} = {msb_first}") });
+        if let Some(size) = order.get(1) {
+            // Read from the tokens, so it must be checked like the parser would
+            let size = match spelled_number(size) {
+                Some((Number::Integer(size), _)) => size,
+                _ => {
+                    refuse(format!("The size of a bitfield must be an integer, not {size:?}"));
+                    "0".to_string()
+                }
+            };
+            lines.push(PyLine { indent_lvl: lvl+1, line: format!("{BITFIELD_SIZE} = {size}") });
+        }
+    }
+
+    lines.extend(vec![
         PyLine { indent_lvl: lvl+1, line: format!(r#"def __init__(self, name: str=""):"#) },
         PyLine { indent_lvl: lvl+2, line: format!(r#"""""#) },
         PyLine { indent_lvl: lvl+2, line: format!(r#"bitfield"#) },
@@ -514,140 +1250,158 @@ pub(crate) fn translate_bitfield(name: Spanned<String>, body: Spanned<Vec<Spanne
         PyLine { indent_lvl: 0, line: format!(r#""#) },
         PyLine { indent_lvl: lvl+2, line: format!(r#"super().__init__(name)"#) },
         PyLine { indent_lvl: 0, line: format!(r#""#) },
-        PyLine { indent_lvl: lvl+1, line: format!(r#"def __matmul__(self, _dollar___offset):"#) },
-        PyLine { indent_lvl: lvl+2, line: format!(r#"if not (isinstance(_dollar___offset, Dollar) or isinstance(_dollar___offset, IntStruct)):"#) },
-        PyLine { indent_lvl: lvl+3, line: format!(r#"raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {{type(_dollar___offset)}} was used instead')"#) },
-        PyLine { indent_lvl: lvl+2, line: format!(r#"if isinstance(_dollar___offset, IntStruct):"#) },
-        PyLine { indent_lvl: lvl+3, line: format!(r#"_dollar___offset = _dollar___offset.to_dollar()"#) },
-        PyLine { indent_lvl: lvl+2, line: format!(r#"_dollar___offset_copy = _dollar___offset.copy()"#) },
-    ];
+        PyLine { indent_lvl: lvl+1, line: format!(r#"def __matmul__(self, {dollar}):"#) },
+        PyLine { indent_lvl: lvl+2, line: format!(r#"if not (isinstance({dollar}, Dollar) or isinstance({dollar}, IntStruct)):"#) },
+        PyLine { indent_lvl: lvl+3, line: format!(r#"raise Exception(f'An object of class "Dollar" must be used with the "@" operator. {{type({dollar})}} was used instead')"#) },
+        PyLine { indent_lvl: lvl+2, line: format!(r#"if isinstance({dollar}, IntStruct):"#) },
+        PyLine { indent_lvl: lvl+3, line: format!(r#"{dollar} = {dollar}.to_dollar()"#) },
+        PyLine { indent_lvl: lvl+2, line: format!(r#"{dollar_copy} = {dollar}.copy()"#) },
+    ]);
 
-    let body = translate_bitfield_body(body.0, lvl+2);
+    let fields = bitfield_fields(&body.0);
+    let bitfield_context = context.push(StatementsContext::Bitfield, fields);
+    let body = vec_translate_statements(body.0, lvl+2, &bitfield_context);
 
+    lines.extend(global_declaration(&bitfield_context, lvl+2));
     lines.extend(body);
 
+    // $ stays at the bit after the last one read
     lines.extend(vec![
-        PyLine { indent_lvl: lvl+2, line: format!(r#"super().init_struct(_dollar___offset_copy, _dollar___offset.copy())"#) },
+        PyLine { indent_lvl: lvl+2, line: format!(r#"super().init_bitfield({dollar_copy}, {dollar})"#) },
         PyLine { indent_lvl: lvl+2, line: format!(r#"return self"#) },
     ]);
 
     PyLines::Multiple(lines)
 }
 
-fn translate_bitfield_body(body: Vec<Spanned<Statement>>, lvl: usize) -> PyLines {
-    let mut lines = Vec::new();
-    let mut bits_read = 0;
-    for stmnt in body {
-        match stmnt.0 {
-            Statement::BitFieldEntry { name, length } => {
-                let name = name.0;
-                let length: usize = translate_expr(length.0, lvl, StatementsContext::Bitfield).unwrap_one().line.parse().expect("Can't use anything other than a number literal as a bitfield entry length");
-                let mut b_size = length;
-                if bits_read == 8 {
-                    bits_read = 0
-                }
-                if bits_read == 0 {
-                    lines.push(PyLine { indent_lvl: lvl, line: "_read__able____bytes: bytes = _dollar___offset.read(1)".into() })
-                }
-                if b_size > 8 || b_size+bits_read > 8 {
-                    lines.push(PyLine { indent_lvl: lvl, line: format!("{name} = 0") });
-                    let mut bit_shift = 0;
-                    while b_size > 8 || (b_size + bits_read) > 8 {
-                        let bits_to_read = 8 - bits_read;
-                        lines.push(PyLine { indent_lvl: lvl, line: format!("{name} += ((_read__able____bytes[0] >> {bits_read}) & self._bit_field___masks_dict[{bits_to_read}]) << {bit_shift}") });
-                        lines.push(PyLine { indent_lvl: lvl, line: format!("_read__able____bytes = _dollar___offset.read(1)") });
-                        bits_read = 0;
-                        b_size -= bits_to_read;
-                        bit_shift += bits_to_read;
-                    }
-                    if b_size > 0 {
-                        lines.push(PyLine { indent_lvl: lvl, line: format!("{name} += (_read__able____bytes[0] & self._bit_field___masks_dict[{length}]) << {bit_shift}") });
-                        bits_read = b_size
-                    } else {
-                        lines.push(PyLine { indent_lvl: lvl, line: format!("{name} >>= 8") });
-                    }
-                    lines.push(PyLine { indent_lvl: lvl, line: format!("self.{name} = {name}") });
-                } else {
-                    lines.push(PyLine { indent_lvl: lvl, line: format!("self.{name} = (_read__able____bytes[0] >> {bits_read}) & self._bit_field___masks_dict[{length}]") });
-                    bits_read += b_size
-                }
-            },
-            _ => todo!() // Some variants are reachable, others aren't
-        }
-    }
-
-    unkown_py_lines(lines)
-}
-
-pub(crate) fn translate_access(item: Box<Spanned<Expr>>, member: Box<Spanned<Expr>>, lvl: usize, context: StatementsContext) -> PyLines {
-    let item = translate_expr(item.0, lvl, context).unwrap_one().line;
-    let member = translate_expr(member.0, lvl, context).unwrap_one().line;
+pub(crate) fn translate_access(item: Box<Spanned<Expr>>, member: Box<Spanned<Expr>>, lvl: usize, context: &Scope) -> PyLines {
+    let item = translate_expr(*item, lvl, context).unwrap_one().line;
+    let member = translate_member(*member, lvl, context);
 
     one_py_line(lvl, format!("{item}.{member}"))
 }
 
-pub(crate) fn translate_array_access(array: Box<Spanned<Expr>>, index: Box<Spanned<Expr>>, lvl: usize, context: StatementsContext) -> PyLines {
-    if context != StatementsContext::None {
-        panic!("Didn't take context into account");
+/// The part after the dot in `a.b`. Unlike other names, it's never a field of the current struct
+fn translate_member(member: Spanned<Expr>, lvl: usize, context: &Scope) -> String {
+    match member {
+        (Expr::Local { name }, _) => match name.0.as_str() {
+            "parent" => "parent()".to_string(),
+            _ => mangle_field(&name.0)
+        },
+        (Expr::Access { item, member }, _) => format!(
+            "{}.{}",
+            translate_member(*item, lvl, context),
+            translate_member(*member, lvl, context)
+        ),
+        member => translate_expr(member, lvl, context).unwrap_one().line
     }
-    let array = translate_expr(array.0, lvl, context).unwrap_one().line;
-    let index = translate_expr(index.0, lvl, context).unwrap_one().line;
+}
+
+pub(crate) fn translate_array_access(array: Box<Spanned<Expr>>, index: Box<Spanned<Expr>>, lvl: usize, context: &Scope) -> PyLines {
+    let array = translate_expr(*array, lvl, context).unwrap_one().line;
+    let index = translate_expr(*index, lvl, context).unwrap_one().line;
 
     one_py_line(lvl, format!("{array}[{index}]"))
 }
 
-pub(crate) fn translate_attribute(arguments: Spanned<Vec<Spanned<Expr>>>, lvl: usize, context: StatementsContext) -> PyLines {
+pub(crate) fn translate_attribute(arguments: Spanned<Vec<Spanned<Expr>>>, lvl: usize, context: &Scope) -> PyLines {
+    // [[color("FF0000"), hidden]] -> {"color": "FF0000", "hidden": True}
     let line = arguments.0.into_iter()
-        .map(|(arg, _)| translate_expr(arg, lvl, context))
         .map(|arg| match arg {
-            PyLines::One(arg) => arg.line,
-            _ => todo!()
-        }).fold(String::new(), |old, new| {
-            format!("{old}, {new}")
-        });
-    one_py_line(lvl, format!("#{line}"))
+            (Expr::Local { name }, _) => format!(r#""{}": True"#, checked(&name.0)),
+            arg => translate_expr(arg, lvl, context).unwrap_one().line,
+        }).collect::<Vec<_>>()
+        .join(", ");
+
+    one_py_line(lvl, format!("{{{line}}}"))
 }
 
-pub(crate) fn translate_attribute_argument(name: Box<Spanned<Expr>>, value: Vec<Spanned<Expr>>, lvl: usize, context: StatementsContext) -> PyLines {
-    let name = translate_expr(name.0, lvl, context).unwrap_one().line;
-    let value = vec_translate_exprs(value, lvl, context).unwrap_one().line;
+pub(crate) fn translate_attribute_argument(name: Box<Spanned<Expr>>, value: Vec<Spanned<Expr>>, lvl: usize, context: &Scope) -> PyLines {
+    let name = checked(&local_name(name.0)).to_string();
+    let takes_function = FUNCTION_ATTRIBUTES.contains(&name.as_str());
+    let mut value = value.into_iter()
+        .map(|arg| match arg {
+            // [[format("std::fmt")]] -> {"format": std.fmt}
+            (Expr::Value { val: Value::Str(function) }, _) if takes_function => mangle_path(&function),
+            arg => translate_expr(arg, lvl, context).unwrap_one().line
+        }).collect::<Vec<_>>();
+
+    let value = match value.len() {
+        0 => "True".to_string(),
+        1 => value.pop().unwrap(),
+        _ => format!("({})", value.join(", "))
+    };
 
-    one_py_line(lvl, format!("#[{name}={value}]"))
+    one_py_line(lvl, format!(r#""{name}": {value}"#))
 }
 
-pub(crate) fn translate_while_loop(condition: Box<Spanned<Expr>>, body: Box<Spanned<Expr>>, lvl: usize, context: StatementsContext) -> PyLines {
-    panic!("Didn't take context into account");
-    let condition = translate_expr(condition.0, lvl, context).unwrap_one().line;
-    let body = translate_expr(body.0, lvl+1, context);
+/// Attributes whose argument is the name of a function, not a string
+const FUNCTION_ATTRIBUTES: [&str; 4] = ["format", "format_read", "format_write", "transform"];
+
+/// Separates the `[[attribute]]` of a definition from its body
+fn split_attribute(body: Spanned<Expr>) -> (Spanned<Expr>, Option<Spanned<Expr>>) {
+    let (body, span) = body;
+    match body {
+        Expr::Attribute { arguments } => (
+            (Expr::Value { val: Value::Null }, span.clone()),
+            Some((Expr::Attribute { arguments }, span))
+        ),
+        Expr::ExprList { mut list } if matches!(list.last(), Some((Expr::Attribute { .. }, _))) => {
+            let attribute = list.pop();
+            let body = match list.len() {
+                1 => list.pop().unwrap(),
+                _ => (Expr::ExprList { list }, span)
+            };
+
+            (body, attribute)
+        },
+        body => ((body, span), None)
+    }
+}
 
-    let mut lines = vec![
-        PyLine { indent_lvl: lvl, line: format!("while {condition}:") }
-    ];
+/// Records the attributes of a definition in the value it defines
+fn with_attributes(value: String, attribute: Option<Spanned<Expr>>, lvl: usize, context: &Scope) -> String {
+    match attribute {
+        Some(attribute) => {
+            let attributes = translate_expr(attribute, lvl, context).unwrap_one().line;
+            format!("({value}).set_attributes({attributes})")
+        },
+        None => value
+    }
+}
 
-    lines.extend(body);
+pub(crate) fn translate_while_loop(condition: Box<Spanned<Expr>>, body: Box<Spanned<Expr>>, lvl: usize, context: &Scope) -> PyLines {
+    // Only used as an array size: u8 a[while($ < 100)]
+    if !is_empty_expr(&body.0) {
+        panic!("Array sizes can't have a loop body")
+    }
+    let condition = translate_expr(*condition, lvl, context).unwrap_one().line;
 
-    PyLines::Multiple(lines)
+    // The array calls it with its own offset before reading each entry
+    one_py_line(lvl, format!("lambda {}: {condition}", generated(DOLLAR)))
 }
 
-pub(crate) fn translate_while_loop_statement(condition: Box<Spanned<Expr>>, body: Spanned<Vec<Spanned<Statement>>>, lvl: usize, context: StatementsContext) -> PyLines {
-    let condition = translate_expr(condition.0, lvl, context).unwrap_one().line;
-    let body = vec_translate_statements(body.0, lvl+1, StatementsContext::WhileLoop);
+pub(crate) fn translate_while_loop_statement(condition: Box<Spanned<Expr>>, body: Spanned<Vec<Spanned<Statement>>>, lvl: usize, context: &Scope) -> PyLines {
+    let condition = translate_expr(*condition, lvl, context).unwrap_one().line;
+    let body = vec_translate_statements(body.0, lvl+1, &context.push(StatementsContext::WhileLoop, HashMap::new()));
 
     let mut lines = vec![
         PyLine { indent_lvl: lvl, line: format!("while {condition}:") }
     ];
 
-    lines.extend(body);
+    lines.extend(block_or_pass(body, lvl+1));
 
     PyLines::Multiple(lines)
 }
 
-pub(crate) fn translate_for_loop(var_init: Box<Spanned<Statement>>, var_test: Box<Spanned<Expr>>, var_change: Box<Spanned<Statement>>, body: Spanned<Vec<Spanned<Statement>>>, lvl: usize, context: StatementsContext) -> PyLines {
-    panic!("Didn't take context into account");
-    let var_init = translate_statement(var_init.0, lvl, context).unwrap_one();
-    let var_test = translate_expr(var_test.0, lvl, context).unwrap_one().line;
-    let var_change = translate_statement(var_change.0, lvl+1, context).unwrap_one();
-    let body = vec_translate_statements(body.0, lvl+1, StatementsContext::ForLoop);
+pub(crate) fn translate_for_loop(var_init: Box<Spanned<Statement>>, var_test: Box<Spanned<Expr>>, var_change: Box<Spanned<Statement>>, body: Spanned<Vec<Spanned<Statement>>>, lvl: usize, context: &Scope) -> PyLines {
+    // The loop variable belongs to the loop
+    let context = &context.push(StatementsContext::ForLoop, HashMap::new());
+    let var_init = translate_statement(*var_init, lvl, context).unwrap_one();
+    let var_test = translate_expr(*var_test, lvl, context).unwrap_one().line;
+    let var_change = translate_statement(*var_change, lvl+1, context).unwrap_one();
+    context.set_loop_step(var_change.line.clone());
+    let body = vec_translate_statements(body.0, lvl+1, context);
 
     let mut lines = vec![
         var_init,
@@ -660,75 +1414,177 @@ pub(crate) fn translate_for_loop(var_init: Box<Spanned<Statement>>, var_test: Bo
     PyLines::Multiple(lines)
 }
 
-pub(crate) fn translate_cast(cast_operator: Spanned<HexTypeDef>, operand: Box<Spanned<Expr>>, lvl: usize, context: StatementsContext) -> PyLines {
-    panic!("Didn't take context into account");
+pub(crate) fn translate_cast(cast_operator: Spanned<HexTypeDef>, operand: Box<Spanned<Expr>>, lvl: usize, context: &Scope) -> PyLines {
     let cast_operator = translate_hextypedef(cast_operator.0, lvl, context).line;
-    let operand = translate_expr(operand.0, lvl, context).unwrap_one().line;
-
-    let line = format!("{cast_operator}({operand})");
+    let operand_is_char = matches!(type_of(operand.as_ref()), InferredType::Char | InferredType::Char16);
+    let operand = translate_expr(*operand, lvl, context).unwrap_one().line;
+    // Chars are python strings
+    let operand = if operand_is_char { format!("ord({operand})") } else { operand };
+
+    // Values are python ints, floats and strings, so casts to builtin types convert them instead of making patterns
+    let line = match cast_operator.as_str() {
+        "Float" | "double" => format!("float({operand})"),
+        "Bool" => format!("bool({operand})"),
+        "char" | "char16" => format!("chr({operand})"),
+        unsigned @ ("u8" | "u16" | "u24" | "u32" | "u48" | "u64" | "u96" | "u128") => format!("hexpat_wrap({operand}, {}, False)", &unsigned[1..]),
+        signed @ ("s8" | "s16" | "s24" | "s32" | "s48" | "s64" | "s96" | "s128") => format!("hexpat_wrap({operand}, {}, True)", &signed[1..]),
+        _ => format!("{cast_operator}({operand})")
+    };
     one_py_line(lvl, line)
 }
 
-pub(crate) fn translate_union(name: Spanned<String>, body: Spanned<Vec<Spanned<Statement>>>, template_parameters: Vec<Spanned<Expr>>, lvl: usize, context: StatementsContext) -> PyLines {
-    panic!("Didn't take context into account");
-    let name = name.0;
-    let body = vec_translate_statements(body.0, lvl+1, StatementsContext::Union);
-
-    let mut lines = if template_parameters.is_empty() {
-        vec![
-            PyLine { indent_lvl: lvl, line: name }
-        ]
-    } else {
-        let template_parameters = vec_translate_exprs(template_parameters, lvl, context).unwrap_one().line;
-        vec![
-            PyLine { indent_lvl: lvl, line: format!("{name}<{template_parameters}>") }
-        ]
-    };
+pub(crate) fn translate_union(name: Spanned<String>, body: Spanned<Vec<Spanned<Statement>>>, template_parameters: Vec<Spanned<Expr>>, lvl: usize, context: &Scope) -> PyLines {
+    if !is_global(context) {
+        return unsupported((name.1.start, body.1.end), "Unions are only allowed on global", lvl)
+    }
+    if let (Some(first), Some(last)) = (template_parameters.first(), template_parameters.last()) {
+        define_type(name.0.clone(), &mangle(&name.0), SymbolKind::Type, context);
+        return unsupported((first.1.start, last.1.end), "Templates aren't supported", lvl)
+    }
+    report_type_attributes(&trailing_attributes(&name, body.1.end), &[]);
+    let hexpat_name = name.0;
+    let name = mangle(&hexpat_name);
+    define_type(hexpat_name, &name, SymbolKind::Type, context);
+    let fields = struct_fields(&body.0, context);
+    // Structs can inherit unions too
+    context.define_struct(context.qualified(&name), fields.clone());
+    let union_context = context.push(StatementsContext::Union, fields);
+    let body = vec_translate_statements(body.0, lvl+2, &union_context);
+
+    let mut lines = struct_class_header(&name, "union", &[], lvl);
+    lines.extend(global_declaration(&union_context, lvl+2));
 
     lines.extend(body);
 
+    lines.push(PyLine { indent_lvl: 0, line: String::new() });
+    lines.extend(struct_end(StatementsContext::Union, lvl+2));
+
     PyLines::Multiple(lines)
 }
 
-pub(crate) fn translate_match(parameters: Vec<Spanned<Expr>>, branches: Vec<MatchBranch>, lvl: usize, context: StatementsContext) -> PyLines {
-    panic!("Didn't take context into account");
-    let parameters = "TODO"; // TODO
-    let branches = "TODO"; // TODO
+pub(crate) fn translate_break(span: (usize, usize), lvl: usize, context: &Scope) -> PyLines {
+    let target = context.enclosing(&[
+        StatementsContext::WhileLoop,
+        StatementsContext::ForLoop,
+        StatementsContext::Struct,
+        StatementsContext::Union,
+        StatementsContext::Function,
+    ]);
 
-    one_py_line(lvl, format!("{parameters} {branches}"))
+    match target {
+        Some(StatementsContext::WhileLoop | StatementsContext::ForLoop) => one_py_line(lvl, "break".to_string()),
+        // Outside of a loop, break stops reading the struct and the array it's in
+        Some(kind @ (StatementsContext::Struct | StatementsContext::Union)) => {
+            let mut lines = vec![PyLine { indent_lvl: lvl, line: "self.break_()".into() }];
+            lines.extend(struct_end(kind, lvl));
+            PyLines::Multiple(lines)
+        },
+        _ => unsupported(span, "break is only allowed inside of loops and structs", lvl)
+    }
 }
 
-pub(crate) fn translate_try_catch(try_block: Spanned<Vec<Spanned<Statement>>>, catch_block: Spanned<Vec<Spanned<Statement>>>, lvl: usize, context: StatementsContext) -> PyLines {
-    panic!("Didn't take context into account");
-    let try_block = vec_translate_statements(try_block.0, lvl, StatementsContext::Try);
-    let catch_block = vec![]; // TODO
+pub(crate) fn translate_continue(span: (usize, usize), lvl: usize, context: &Scope) -> PyLines {
+    let target = context.enclosing(&[
+        StatementsContext::WhileLoop,
+        StatementsContext::ForLoop,
+        StatementsContext::Struct,
+        StatementsContext::Union,
+        StatementsContext::Function,
+    ]);
+
+    match target {
+        Some(StatementsContext::WhileLoop | StatementsContext::ForLoop) => match context.loop_step() {
+            // The step of a for loop is at the end of its body, which continue skips
+            Some(step) => PyLines::Multiple(vec![
+                PyLine { indent_lvl: lvl, line: step },
+                PyLine { indent_lvl: lvl, line: "continue".into() },
+            ]),
+            None => one_py_line(lvl, "continue".to_string()),
+        },
+        // Outside of a loop, continue stops reading the struct and leaves it out of the array it's in
+        Some(kind @ (StatementsContext::Struct | StatementsContext::Union)) => {
+            let mut lines = vec![PyLine { indent_lvl: lvl, line: "self.continue_()".into() }];
+            lines.extend(struct_end(kind, lvl));
+            PyLines::Multiple(lines)
+        },
+        _ => unsupported(span, "continue is only allowed inside of loops and structs", lvl)
+    }
+}
+
+pub(crate) fn translate_match(parameters: Vec<Spanned<Expr>>, branches: Vec<MatchBranch>, lvl: usize, context: &Scope) -> PyLines {
+    let span = match (parameters.first(), parameters.last()) {
+        (Some(first), Some(last)) => (first.1.start, last.1.end),
+        _ => (0, 0)
+    };
+    unsupported(span, "Match statements aren't supported", lvl)
+}
+
+pub(crate) fn translate_try_catch(try_block: Spanned<Vec<Spanned<Statement>>>, catch_block: Spanned<Vec<Spanned<Statement>>>, lvl: usize, context: &Scope) -> PyLines {
+    let try_block = vec_translate_statements(try_block.0, lvl+1, &context.push(StatementsContext::Try, HashMap::new()));
+    let catch_block = vec_translate_statements(catch_block.0, lvl+1, &context.push(StatementsContext::Try, HashMap::new()));
 
     let mut lines = vec![
         PyLine{indent_lvl: lvl, line: "try:".to_string()}
     ];
-    lines.extend(try_block.into_iter());
+    lines.extend(block_or_pass(try_block, lvl+1));
     lines.extend(vec![
         PyLine{indent_lvl: lvl, line: "except Exception:".to_string()}
     ]);
-    lines.extend(catch_block.into_iter());
+    lines.extend(block_or_pass(catch_block, lvl+1));
 
     PyLines::Multiple(lines)
 }
 
-fn translate_arg(arg: FuncArgument, lvl: usize, context: StatementsContext) -> PyLine {
-    panic!("Didn't take context into account");
+/// The lines of an indented block. Python doesn't allow empty blocks, so those become `pass`
+fn block_or_pass(body: PyLines, lvl: usize) -> Vec<PyLine> {
+    match body {
+        PyLines::None => vec![PyLine { indent_lvl: lvl, line: "pass".into() }],
+        body => body.into_iter().collect()
+    }
+}
+
+/// Whether the parser left this expression empty (`u8 a;`, `char s[];`)
+pub(crate) fn is_empty_expr(expr: &Expr) -> bool {
+    match expr {
+        Expr::Value { val: Value::Null } => true,
+        Expr::ExprList { list } => list.is_empty(),
+        _ => false
+    }
+}
+
+/// A parameter of a function, which is defined as a local of its body
+fn translate_arg(arg: FuncArgument, lvl: usize, context: &Scope) -> PyLine {
     match arg {
-        FuncArgument::Parameter(par) => {
-            match translate_expr(par.0, lvl, context) {
-                PyLines::One(line) => line,
-                _ => unreachable!()
-            }
+        FuncArgument::Parameter(par) => match par.0 {
+            // u8 value = 5
+            Expr::Definition(Definition { value_type, name, body }) => {
+                let hexpat_name = local_name(name.0);
+                let value_type = translate_hextypedef(value_type.0, lvl, context).line;
+                context.define(hexpat_name.clone(), Symbol { kind: SymbolKind::Local, value_type });
+                let name = mangle(&hexpat_name);
+                let line = if is_empty_expr(&body.0) {
+                    name
+                } else {
+                    let default = translate_expr(*body, lvl, context).unwrap_one().line;
+                    format!("{name}={default}")
+                };
+                PyLine { indent_lvl: lvl, line }
+            },
+            _ => panic!("Parameters must be definitions")
+        },
+        // auto ... values
+        FuncArgument::ParameterPack((pack, _)) => {
+            context.define(pack.clone(), Symbol { kind: SymbolKind::Local, value_type: "auto".to_string() });
+            PyLine { indent_lvl: lvl, line: format!("*{}", mangle(&pack)) }
         },
-        FuncArgument::ParameterPack((pack, _)) => PyLine{indent_lvl: lvl, line: pack},
     }
 }
 
-pub(crate) fn translate_hextypedef(value_type: HexTypeDef, lvl: usize, context: StatementsContext) -> PyLine {
+fn is_big_endian(value_type: &HexTypeDef) -> bool {
+    matches!(value_type.endianness, Endianness::Big)
+}
+
+pub(crate) fn translate_hextypedef(value_type: HexTypeDef, lvl: usize, context: &Scope) -> PyLine {
     let HexTypeDef {
         endianness,
         name,
@@ -1,11 +1,13 @@
 use std::path::PathBuf;
 
 use expr_translator::vec_translate_statements;
-use pyo3::prelude::*;
+use pyo3::{prelude::*, exceptions::PyValueError};
 
-use expr_translator::StatementsContext;
+use expr_translator::{Scope, set_source, take_unsupported, take_refused, identifiers::{set_used_names, generated, DOLLAR, BYTES}};
+use type_inference::{infer, set_types, Diagnostic};
 
 mod expr_translator;
+mod type_inference;
 
 struct PyLine {
     indent_lvl: usize,
@@ -56,14 +58,29 @@ fn rs_hexpyt(py: Python, m: &PyModule) -> PyResult<()> {
     indentation="    ",
     extra_paths=Vec::new()
 ))]
-fn translate_file(input_file_path: PathBuf, output_file_path: PathBuf, indentation: &str, extra_paths: Vec<String>) -> PyResult<()> {
+/// Returns the type errors and what couldn't be translated, as (start, end, message).
+/// Raises ValueError for patterns whose translation would run code from them
+fn translate_file(input_file_path: PathBuf, output_file_path: PathBuf, indentation: &str, extra_paths: Vec<String>) -> PyResult<Vec<(usize, usize, String)>> {
     let input_file = std::fs::read_to_string(input_file_path)?;
 
     let (ast, errors, _) = hexparser::parse(&input_file, &extra_paths);
+    // A translation that panicked or failed to write leaves what it reported behind
+    take_refused();
+    take_unsupported();
+    set_source(&input_file);
+    set_used_names(&input_file);
+    let (types, diagnostics) = infer(&ast.0);
+    set_types(types);
 
     let mut output_file = get_header();
 
-    for stmnt in vec_translate_statements(ast.0, 0, StatementsContext::None) {
+    let lines = with_blank_lines(vec_translate_statements(ast.0, 0, &Scope::global()));
+    let unsupported = take_unsupported();
+    if let Some(reason) = take_refused() {
+        return Err(PyValueError::new_err(format!("Refused to translate the pattern: {reason}")))
+    }
+
+    for stmnt in lines {
         let indent = indentation.repeat(stmnt.indent_lvl as usize);
         let line = &stmnt.line;
         output_file = format!("{output_file}{indent}{line}\n");
@@ -71,7 +88,10 @@ fn translate_file(input_file_path: PathBuf, output_file_path: PathBuf, indentati
 
     std::fs::write(output_file_path, output_file)?;
 
-    Ok(())
+    Ok(diagnostics.into_iter()
+        .chain(unsupported)
+        .map(|Diagnostic { start, end, message }| (start, end, message))
+        .collect())
 }
 
 fn get_header() -> String {
@@ -79,24 +99,58 @@ fn get_header() -> String {
     final_string += "u8, u16, u24, u32, u48, u64, u96, u128, ";
     final_string += "s8, s16, s24, s32, s48, s64, s96, s128, ";
     final_string += "Float, double, char, char16, Bool, ";
-    final_string += "Padding, Array, Enum, sizeof, addressof\n";
-    final_string += r#"
+    final_string += "Padding, Array, String, Enum, namespace, sizeof, addressof, hexpat_div, hexpat_wrap, placed\n";
+    let (dollar, byts) = (generated(DOLLAR), generated(BYTES));
+    final_string += &format!(r#"
+# Every pattern placed outside of structs, in the order they were placed. Defining a name again doesn't remove the previous pattern
+placed_patterns: list[tuple[str, Struct]] = []
+
 # Template to read from a file. follow the instructions.
-# _dollar___offset has this name so it doesn't clash with others. Feel free to rename it.
+# {dollar} has this name so it doesn't clash with others. Feel free to rename it.
 if True: # Change this from "if True" to "if False", then put the file path below.
-    byts = b''
+    {byts} = b''
 else:
     file_path = "" # Put the file path here and change the above "if True" to "if False".
     with open(file_path, "rb") as f:
-        byts = f.read()
-_dollar___offset = Dollar(0x00, byts)
+        {byts} = f.read()
+{dollar} = Dollar(0x00, {byts})
 # End of template
 
-"#;
+"#);
 
     return final_string
 }
 
+/// `lines` with a blank line around each class and function, unless it's the first thing in its block
+fn with_blank_lines(lines: PyLines) -> Vec<PyLine> {
+    let mut spaced: Vec<PyLine> = Vec::new();
+    // Indentation of the classes and functions whose body is being written
+    let mut open_blocks: Vec<usize> = Vec::new();
+    for line in lines {
+        let after_blank = matches!(spaced.last(), None | Some(PyLine { line: last, .. }) if last.is_empty());
+        if line.line.is_empty() {
+            if !after_blank {
+                spaced.push(line);
+            }
+            continue
+        }
+
+        let closes_block = open_blocks.last().map_or(false, |&indent| line.indent_lvl <= indent);
+        open_blocks.retain(|&indent| indent < line.indent_lvl);
+        let opens_block = line.line.starts_with("class ") || line.line.starts_with("def ");
+        let first_in_block = spaced.last().map_or(false, |last| last.indent_lvl < line.indent_lvl);
+        if !after_blank && (closes_block || (opens_block && !first_in_block)) {
+            spaced.push(PyLine { indent_lvl: 0, line: String::new() });
+        }
+        if opens_block {
+            open_blocks.push(line.indent_lvl);
+        }
+        spaced.push(line);
+    }
+
+    spaced
+}
+
 fn one_py_line(lvl: usize, line: String) -> PyLines {
     PyLines::One(
         PyLine {
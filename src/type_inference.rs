@@ -0,0 +1,605 @@
+//! Types of hexpat expressions, worked out before translating so the translation can depend on them.
+//! Only what is known without running the pattern is inferred, anything else is `Unknown`
+
+use std::{cell::RefCell, collections::{HashMap, HashSet}};
+
+use hexparser::{Expr, Value, m_parser::{Statement, FuncCall, Definition, HexType, HexTypeDef, BinaryOp, UnaryOp, AssignmentOp, FuncArgument}, token::{Spanned, ValueType}};
+
+use crate::expr_translator::{struct_parents, is_initialization, literals, tokens::is_token_span, identifiers::mangle};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum InferredType {
+    /// u8 to s128
+    Integer { bits: usize, signed: bool },
+    /// A number written in the pattern. It's never negative, `-1` is the negation of a literal
+    IntegerLiteral,
+    /// float (32 bits) or double (64 bits)
+    Float { bits: usize },
+    Bool,
+    Char,
+    Char16,
+    Str,
+    Array(Box<InferredType>),
+    /// Structs, unions, enums and bitfields, by their whole python path, `my.Header`
+    Custom(String),
+    Unknown,
+}
+
+impl InferredType {
+    pub(crate) fn is_float(&self) -> bool {
+        matches!(self, InferredType::Float { .. })
+    }
+
+    /// Integers that are never negative, so python's floor division is the same as hexpat's
+    pub(crate) fn is_unsigned(&self) -> bool {
+        matches!(self, InferredType::Integer { signed: false, .. } | InferredType::IntegerLiteral)
+    }
+
+    /// Bits and signedness of values that wrap around. Chars are python strings, so they aren't wrapped
+    pub(crate) fn width(&self) -> Option<(usize, bool)> {
+        match self {
+            InferredType::Integer { bits, signed } => Some((*bits, *signed)),
+            _ => None
+        }
+    }
+
+    /// rs_hexpyt's name of the type
+    pub(crate) fn python_name(&self) -> Option<String> {
+        let name = match self {
+            InferredType::Integer { bits, signed: false } => format!("u{bits}"),
+            InferredType::Integer { bits, signed: true } => format!("s{bits}"),
+            InferredType::Float { bits: 32 } => "Float".to_string(),
+            InferredType::Float { .. } => "double".to_string(),
+            InferredType::Bool => "Bool".to_string(),
+            InferredType::Char => "char".to_string(),
+            InferredType::Char16 => "char16".to_string(),
+            InferredType::Str => "str".to_string(),
+            InferredType::Array(element) => format!("Array[{}]", element.python_name()?),
+            InferredType::Custom(name) => name.clone(),
+            InferredType::IntegerLiteral | InferredType::Unknown => return None,
+        };
+
+        Some(name)
+    }
+
+    /// Name of the type for diagnostics
+    fn describe(&self) -> String {
+        match self {
+            InferredType::IntegerLiteral => "integer".to_string(),
+            InferredType::Str => "string".to_string(),
+            _ => self.python_name().unwrap_or_else(|| "unknown".to_string())
+        }
+    }
+
+    fn is_number(&self) -> bool {
+        matches!(self,
+            InferredType::Integer { .. }
+            | InferredType::IntegerLiteral
+            | InferredType::Float { .. }
+            | InferredType::Bool
+            | InferredType::Char
+            | InferredType::Char16
+        )
+    }
+}
+
+/// A type error at `start..end` of the source
+#[derive(Debug, Clone)]
+pub(crate) struct Diagnostic {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) message: String,
+}
+
+thread_local! {
+    /// Types of the expressions of the file being translated, by span
+    static TYPES: RefCell<HashMap<(usize, usize), InferredType>> = RefCell::new(HashMap::new());
+}
+
+pub(crate) fn set_types(types: HashMap<(usize, usize), InferredType>) {
+    TYPES.with(|t| *t.borrow_mut() = types)
+}
+
+/// Inferred type of an expression of the file being translated
+pub(crate) fn type_of<T>(spanned: &Spanned<T>) -> InferredType {
+    TYPES.with(|types| types.borrow()
+        .get(&(spanned.1.start, spanned.1.end))
+        .cloned()
+        .unwrap_or(InferredType::Unknown)
+    )
+}
+
+/// Types of the expressions in `stmnts` by span, and the type errors in them
+pub(crate) fn infer(stmnts: &[Spanned<Statement>]) -> (HashMap<(usize, usize), InferredType>, Vec<Diagnostic>) {
+    let mut inference = Inference {
+        types: HashMap::new(),
+        diagnostics: Vec::new(),
+        scopes: vec![HashMap::new()],
+        aliases: HashMap::new(),
+        fields: HashMap::new(),
+        defined_types: HashSet::new(),
+        namespace: Vec::new(),
+        foreign: false,
+    };
+    for stmnt in stmnts {
+        inference.foreign = !is_token_span(stmnt.1.start, stmnt.1.end);
+        inference.statement(stmnt);
+    }
+
+    (inference.types, inference.diagnostics)
+}
+
+struct Inference {
+    types: HashMap<(usize, usize), InferredType>,
+    diagnostics: Vec<Diagnostic>,
+    /// Variables and fields, the innermost scope last. Like when translating, loops and ifs don't have their own
+    scopes: Vec<HashMap<String, InferredType>>,
+    /// using New = Old; by the whole python path of New
+    aliases: HashMap<String, InferredType>,
+    /// Types of the fields of each struct, union and bitfield, by its whole python path
+    fields: HashMap<String, HashMap<String, InferredType>>,
+    /// Whole python paths of the types defined so far
+    defined_types: HashSet<String>,
+    /// Hexpat path of the namespace being inferred
+    namespace: Vec<String>,
+    /// Whether the statement being inferred is from an included file. Its spans point into that file, so its types
+    /// aren't kept where they could be mistaken for the types of this one
+    foreign: bool,
+}
+
+impl Inference {
+    fn statements(&mut self, stmnts: &[Spanned<Statement>]) {
+        for stmnt in stmnts {
+            self.statement(stmnt)
+        }
+    }
+
+    /// Names defined in the statements of a struct, union or bitfield, on top of the `inherited` ones
+    fn block(&mut self, stmnts: &[Spanned<Statement>], inherited: HashMap<String, InferredType>) -> HashMap<String, InferredType> {
+        self.scopes.push(inherited);
+        self.statements(stmnts);
+        self.scopes.pop().expect("The scope was just pushed")
+    }
+
+    fn statement(&mut self, stmnt: &Spanned<Statement>) {
+        match &stmnt.0 {
+            Statement::Call(FuncCall { arguments, .. }) => for argument in &arguments.0 {
+                self.expr(argument);
+            },
+            Statement::If { test, consequent } => {
+                self.expr(test);
+                self.statements(&consequent.0);
+            },
+            Statement::IfBlock { ifs, alternative } => {
+                self.statements(&ifs.0);
+                self.statements(&alternative.0);
+            },
+            Statement::ArrayDefinition { value_type, array_name, size, body } => {
+                self.expr(size);
+                self.expr(body);
+                let element = self.hextypedef(&value_type.0);
+                if let Expr::Local { name } = &array_name.0 {
+                    self.record((array_name.1.start, array_name.1.end), InferredType::Array(Box::new(element.clone())));
+                    self.define(&name.0, InferredType::Array(Box::new(element)));
+                }
+            },
+            Statement::Using { new_name, old_name, .. } => {
+                let old_type = self.hextypedef(&old_name.0);
+                let path = self.define_type(&new_name.0);
+                self.aliases.insert(path, old_type);
+            },
+            Statement::Return { value } => {
+                self.expr(value);
+            },
+            Statement::Continue | Statement::Break | Statement::Error => (),
+            Statement::Func { args, body, .. } => {
+                self.scopes.push(HashMap::new());
+                for (arg, _) in &args.0 {
+                    if let FuncArgument::Parameter(parameter) = arg {
+                        self.expr(parameter);
+                    }
+                }
+                self.statements(&body.0);
+                self.scopes.pop();
+            },
+            Statement::Struct { name, body, .. } => {
+                let inherited = struct_parents(name, body.1.start).iter()
+                    .filter_map(|(parent, _)| self.fields.get(&self.resolve_type(parent)))
+                    .flat_map(|fields| fields.clone())
+                    .collect();
+                let path = self.define_type(&name.0);
+                let fields = self.block(&body.0, inherited);
+                self.fields.insert(path, fields);
+            },
+            Statement::Union { name, body, .. }
+            | Statement::Bitfield { name, body } => {
+                let path = self.define_type(&name.0);
+                let fields = self.block(&body.0, HashMap::new());
+                self.fields.insert(path, fields);
+            },
+            Statement::Namespace { name, body } => {
+                let depth = self.namespace.len();
+                self.namespace.extend(hexpat_path(&name.0));
+                self.statements(&body.0);
+                self.namespace.truncate(depth);
+            },
+            Statement::Enum { name, body, .. } => {
+                self.define_type(&name.0);
+                for (entry, _) in &body.0 {
+                    if let Expr::EnumEntry { value, .. } = entry {
+                        self.expr(value);
+                    }
+                }
+            },
+            Statement::ForLoop { var_init, var_test, var_change, body } => {
+                self.statement(var_init);
+                self.expr(var_test);
+                self.statement(var_change);
+                self.statements(&body.0);
+            },
+            Statement::WhileLoop { condition, body } => {
+                self.expr(condition);
+                self.statements(&body.0);
+            },
+            Statement::TryCatch { try_block, catch_block } => {
+                self.statements(&try_block.0);
+                self.statements(&catch_block.0);
+            },
+            // Not translated yet
+            Statement::Match { .. } => (),
+            Statement::Definition(definition) => {
+                self.definition(definition);
+            },
+            Statement::Assignment { loperand, operator, roperand } => self.assignment(loperand, operator, roperand),
+            Statement::Padding { padding_body } => {
+                self.expr(padding_body);
+            },
+            Statement::BitFieldEntry { name, length } => {
+                self.expr(length);
+                self.define(&name.0, InferredType::Unknown);
+            },
+        }
+    }
+
+    fn expr(&mut self, expr: &Spanned<Expr>) -> InferredType {
+        let span = (expr.1.start, expr.1.end);
+        let inferred = match &expr.0 {
+            Expr::Error => InferredType::Unknown,
+            Expr::Value { val } => literal_type(val, span),
+            Expr::ExprList { list } => {
+                let types = list.iter().map(|expr| self.expr(expr)).collect::<Vec<_>>();
+                match types.len() {
+                    1 => types[0].clone(),
+                    _ => InferredType::Unknown
+                }
+            },
+            Expr::UnnamedParameter { .. } => InferredType::Unknown,
+            Expr::Local { name } => self.lookup(&name.0),
+            Expr::Unary { operation, operand } => {
+                let operand = self.expr(operand);
+                self.unary(operation, operand, span)
+            },
+            Expr::Binary { loperand, operator, roperand } => {
+                let ltype = self.expr(loperand);
+                let rtype = self.expr(roperand);
+                self.binary(operator, &ltype, &rtype, span)
+            },
+            Expr::Ternary { loperand, moperand, roperand } => {
+                self.expr(loperand);
+                let mtype = self.expr(moperand);
+                let rtype = self.expr(roperand);
+                match mtype == rtype {
+                    true => mtype,
+                    false => InferredType::Unknown
+                }
+            },
+            Expr::Call(FuncCall { arguments, .. }) => {
+                for argument in &arguments.0 {
+                    self.expr(argument);
+                }
+                InferredType::Unknown
+            },
+            Expr::Definition(definition) => self.definition(definition),
+            Expr::EnumEntry { value, .. } => {
+                self.expr(value);
+                InferredType::Unknown
+            },
+            Expr::NamespaceAccess { .. } => InferredType::Unknown,
+            // header.size
+            Expr::Access { item, member } => match (self.expr(item), &member.0) {
+                (InferredType::Custom(item), Expr::Local { name }) => self.fields.get(&item)
+                    .and_then(|fields| fields.get(&name.0))
+                    .cloned()
+                    .unwrap_or(InferredType::Unknown),
+                _ => InferredType::Unknown
+            },
+            Expr::ArrayAccess { array, index } => {
+                self.expr(index);
+                match self.expr(array) {
+                    InferredType::Array(element) => *element,
+                    _ => InferredType::Unknown
+                }
+            },
+            Expr::Attribute { .. } | Expr::AttributeArgument { .. } => InferredType::Unknown,
+            Expr::WhileLoop { condition, body } => {
+                self.expr(condition);
+                self.expr(body);
+                InferredType::Unknown
+            },
+            Expr::Cast { cast_operator, operand } => {
+                self.expr(operand);
+                self.hextypedef(&cast_operator.0)
+            },
+            // sizeof(u8)
+            Expr::Type { .. } => InferredType::Unknown,
+        };
+
+        self.record(span, inferred.clone());
+        inferred
+    }
+
+    fn record(&mut self, span: (usize, usize), inferred: InferredType) {
+        if !self.foreign {
+            self.types.insert(span, inferred);
+        }
+    }
+
+    fn definition(&mut self, Definition { value_type, name, body }: &Definition) -> InferredType {
+        let declared = self.hextypedef(&value_type.0);
+        let body_type = self.expr(body);
+        let is_initialization = is_initialization(name, body.1.start);
+
+        let defined = match (&value_type.0.name.0, body_type) {
+            // Literals are 128 bits in hexpat
+            (HexType::V(ValueType::Auto), InferredType::IntegerLiteral) => InferredType::Integer { bits: 128, signed: false },
+            (HexType::V(ValueType::Auto), body_type) => body_type,
+            (_, body_type) => {
+                if is_initialization && !compatible(&declared, &body_type) {
+                    self.error((body.1.start, body.1.end), format!("Can't initialize a {} with a {}", declared.describe(), body_type.describe()));
+                }
+                declared
+            }
+        };
+
+        if let Expr::Local { name: local } = &name.0 {
+            self.record((name.1.start, name.1.end), defined.clone());
+            self.define(&local.0, defined.clone());
+        }
+
+        defined
+    }
+
+    fn assignment(&mut self, loperand: &Spanned<Expr>, operator: &AssignmentOp, roperand: &Spanned<Expr>) {
+        let ltype = self.expr(loperand);
+        let rtype = self.expr(roperand);
+        let span = (loperand.1.start, roperand.1.end);
+        let operator = match operator {
+            AssignmentOp::Just => {
+                if !compatible(&ltype, &rtype) {
+                    self.error((roperand.1.start, roperand.1.end), format!("Can't assign a {} to a {}", rtype.describe(), ltype.describe()));
+                }
+                return
+            },
+            AssignmentOp::Add => BinaryOp::Add,
+            AssignmentOp::Sub => BinaryOp::Sub,
+            AssignmentOp::Mul => BinaryOp::Mul,
+            AssignmentOp::Div => BinaryOp::Div,
+            AssignmentOp::Mod => BinaryOp::Mod,
+            AssignmentOp::RShift => BinaryOp::RShift,
+            AssignmentOp::LShift => BinaryOp::LShift,
+            AssignmentOp::BOr => BinaryOp::BOr,
+            AssignmentOp::BAnd => BinaryOp::BAnd,
+            AssignmentOp::BXor => BinaryOp::BXor,
+        };
+
+        self.binary(&operator, &ltype, &rtype, span);
+    }
+
+    fn unary(&mut self, operation: &UnaryOp, operand: InferredType, span: (usize, usize)) -> InferredType {
+        match (operation, operand) {
+            (UnaryOp::LNot, _) => InferredType::Bool,
+            (_, InferredType::Str) => {
+                self.error(span, "Strings can't be used in arithmetic".to_string());
+                InferredType::Unknown
+            },
+            (UnaryOp::BNot, operand) if operand.is_float() => {
+                self.error(span, format!("~ needs an integer, not a {}", operand.describe()));
+                InferredType::Unknown
+            },
+            (UnaryOp::Sub, InferredType::IntegerLiteral) => InferredType::Integer { bits: 128, signed: true },
+            (_, operand) => operand
+        }
+    }
+
+    fn binary(&mut self, operator: &BinaryOp, ltype: &InferredType, rtype: &InferredType, span: (usize, usize)) -> InferredType {
+        let is_text = |t: &InferredType| matches!(t, InferredType::Str | InferredType::Char | InferredType::Unknown);
+        match operator {
+            BinaryOp::Eq
+            | BinaryOp::NotEq
+            | BinaryOp::GreaterEqual
+            | BinaryOp::LessEqual
+            | BinaryOp::Greater
+            | BinaryOp::Less
+            | BinaryOp::LAnd
+            | BinaryOp::LXor
+            | BinaryOp::LOr => InferredType::Bool,
+            BinaryOp::Assign(_) => rtype.clone(),
+            // "a" + "b", "a" + 'b'
+            BinaryOp::Add if (*ltype == InferredType::Str && is_text(rtype)) || (*rtype == InferredType::Str && is_text(ltype)) => InferredType::Str,
+            _ if *ltype == InferredType::Str || *rtype == InferredType::Str => {
+                self.error(span, format!("Strings can't be used with {}", operator_symbol(operator)));
+                InferredType::Unknown
+            },
+            BinaryOp::BAnd
+            | BinaryOp::BOr
+            | BinaryOp::BXor
+            | BinaryOp::LShift
+            | BinaryOp::RShift if ltype.is_float() || rtype.is_float() => {
+                self.error(span, format!("{} needs integers, not a {} and a {}", operator_symbol(operator), ltype.describe(), rtype.describe()));
+                InferredType::Unknown
+            },
+            BinaryOp::LShift | BinaryOp::RShift => ltype.clone(),
+            _ => arithmetic_type(ltype, rtype)
+        }
+    }
+
+    fn hextypedef(&self, value_type: &HexTypeDef) -> InferredType {
+        self.hextype(&value_type.name.0)
+    }
+
+    fn hextype(&self, htype: &HexType) -> InferredType {
+        match htype {
+            HexType::Custom(name) => self.custom_type(name),
+            HexType::Path(path) => self.custom_type(&path.join("::")),
+            HexType::V(v) => match v {
+                ValueType::U8 => InferredType::Integer { bits: 8, signed: false },
+                ValueType::U16 => InferredType::Integer { bits: 16, signed: false },
+                ValueType::U24 => InferredType::Integer { bits: 24, signed: false },
+                ValueType::U32 => InferredType::Integer { bits: 32, signed: false },
+                ValueType::U48 => InferredType::Integer { bits: 48, signed: false },
+                ValueType::U64 => InferredType::Integer { bits: 64, signed: false },
+                ValueType::U96 => InferredType::Integer { bits: 96, signed: false },
+                ValueType::U128 => InferredType::Integer { bits: 128, signed: false },
+                ValueType::S8 => InferredType::Integer { bits: 8, signed: true },
+                ValueType::S16 => InferredType::Integer { bits: 16, signed: true },
+                ValueType::S24 => InferredType::Integer { bits: 24, signed: true },
+                ValueType::S32 => InferredType::Integer { bits: 32, signed: true },
+                ValueType::S48 => InferredType::Integer { bits: 48, signed: true },
+                ValueType::S64 => InferredType::Integer { bits: 64, signed: true },
+                ValueType::S96 => InferredType::Integer { bits: 96, signed: true },
+                ValueType::S128 => InferredType::Integer { bits: 128, signed: true },
+                ValueType::Float => InferredType::Float { bits: 32 },
+                ValueType::Double => InferredType::Float { bits: 64 },
+                ValueType::Boolean => InferredType::Bool,
+                ValueType::Character => InferredType::Char,
+                ValueType::Character16 => InferredType::Char16,
+                ValueType::String => InferredType::Str,
+                ValueType::Auto | ValueType::Padding | ValueType::CustomType => InferredType::Unknown,
+            },
+            HexType::Parameted(htype, _) => self.hextype(htype),
+            HexType::Null => InferredType::Unknown,
+        }
+    }
+
+    /// The type `name` refers to, with aliases resolved
+    fn custom_type(&self, name: &str) -> InferredType {
+        let path = self.resolve_type(name);
+        self.aliases.get(&path)
+            .cloned()
+            .unwrap_or(InferredType::Custom(path))
+    }
+
+    /// Whole python path of the type `name` defines in the namespace being inferred
+    fn define_type(&mut self, name: &str) -> String {
+        let path = python_path(self.namespace.iter().map(String::as_str).chain(std::iter::once(name)));
+        self.defined_types.insert(path.clone());
+        path
+    }
+
+    /// Whole python path of the type `name` (`a::B`) refers to, looking from the innermost namespace outwards
+    fn resolve_type(&self, name: &str) -> String {
+        (0..=self.namespace.len()).rev()
+            .map(|depth| python_path(self.namespace[..depth].iter().map(String::as_str).chain(name.split("::"))))
+            .find(|path| self.defined_types.contains(path))
+            .unwrap_or_else(|| python_path(name.split("::")))
+    }
+
+    fn define(&mut self, name: &str, value_type: InferredType) {
+        self.scopes.last_mut()
+            .expect("The global scope is never popped")
+            .insert(name.to_string(), value_type);
+    }
+
+    fn lookup(&self, name: &str) -> InferredType {
+        self.scopes.iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .cloned()
+            .unwrap_or(InferredType::Unknown)
+    }
+
+    fn error(&mut self, (start, end): (usize, usize), message: String) {
+        self.diagnostics.push(Diagnostic { start, end, message })
+    }
+}
+
+/// Python path of the segments of a hexpat path
+fn python_path<'a>(segments: impl Iterator<Item = &'a str>) -> String {
+    segments.map(mangle).collect::<Vec<_>>().join(".")
+}
+
+/// Segments of a namespace name, `a::b` -> `["a", "b"]`
+fn hexpat_path(expr: &Expr) -> Vec<String> {
+    match expr {
+        Expr::Local { name } => vec![name.0.clone()],
+        Expr::NamespaceAccess { previous, name } => {
+            let mut path = hexpat_path(&previous.0);
+            path.push(name.0.clone());
+            path
+        },
+        _ => Vec::new()
+    }
+}
+
+fn literal_type(val: &Value, span: (usize, usize)) -> InferredType {
+    match val {
+        Value::Null | Value::Func(_) => InferredType::Unknown,
+        Value::Bool(_) => InferredType::Bool,
+        Value::Num(n) if literals::number(*n, span).is_float() => InferredType::Float { bits: 64 },
+        Value::Num(_) => InferredType::IntegerLiteral,
+        Value::Str(_) => InferredType::Str,
+        Value::Char(_) => InferredType::Char,
+    }
+}
+
+/// Type of the result of +, -, *, /, %, &, | and ^. The widest operand wins, and floats win over integers
+fn arithmetic_type(ltype: &InferredType, rtype: &InferredType) -> InferredType {
+    match (ltype, rtype) {
+        (InferredType::Float { bits: lbits }, InferredType::Float { bits: rbits }) => InferredType::Float { bits: *lbits.max(rbits) },
+        (InferredType::Float { .. }, InferredType::Unknown) | (InferredType::Unknown, InferredType::Float { .. }) => InferredType::Unknown,
+        (InferredType::Float { .. }, _) => ltype.clone(),
+        (_, InferredType::Float { .. }) => rtype.clone(),
+        (
+            InferredType::Integer { bits: lbits, signed: lsigned },
+            InferredType::Integer { bits: rbits, signed: rsigned }
+        ) => InferredType::Integer { bits: *lbits.max(rbits), signed: *lsigned || *rsigned },
+        (InferredType::Integer { .. }, InferredType::IntegerLiteral) => ltype.clone(),
+        (InferredType::IntegerLiteral, InferredType::Integer { .. }) => rtype.clone(),
+        (InferredType::IntegerLiteral, InferredType::IntegerLiteral) => InferredType::IntegerLiteral,
+        _ => InferredType::Unknown
+    }
+}
+
+/// Whether a value of type `value` can be stored in a variable of type `variable`
+fn compatible(variable: &InferredType, value: &InferredType) -> bool {
+    match (variable, value) {
+        (InferredType::Str, value) => !value.is_number(),
+        (variable, InferredType::Str) => !variable.is_number(),
+        _ => true
+    }
+}
+
+fn operator_symbol(operator: &BinaryOp) -> &'static str {
+    match operator {
+        BinaryOp::Add => "+",
+        BinaryOp::Sub => "-",
+        BinaryOp::Mul => "*",
+        BinaryOp::Div => "/",
+        BinaryOp::Eq => "==",
+        BinaryOp::NotEq => "!=",
+        BinaryOp::Mod => "%",
+        BinaryOp::LShift => "<<",
+        BinaryOp::RShift => ">>",
+        BinaryOp::BAnd => "&",
+        BinaryOp::BXor => "^",
+        BinaryOp::BOr => "|",
+        BinaryOp::GreaterEqual => ">=",
+        BinaryOp::LessEqual => "<=",
+        BinaryOp::Greater => ">",
+        BinaryOp::Less => "<",
+        BinaryOp::LAnd => "&&",
+        BinaryOp::LXor => "^^",
+        BinaryOp::LOr => "||",
+        BinaryOp::Assign(_) => "=",
+    }
+}